@@ -1,8 +1,13 @@
 #![no_std]
 
+mod scheduler;
 mod storage_dao_collection;
 mod storage_dao_map;
+mod storage_membership;
+mod storage_pending_operations;
 mod storage_proposal_map;
+mod storage_treasury;
+mod vft_calls;
 
 use gstd::{debug, prog};
 
@@ -12,48 +17,260 @@ pub use code::WASM_BINARY_OPT as WASM_BINARY;
 use gstd::collections::HashMap;
 use gstd::{exec, msg};
 use sails_rs::prelude::*;
-use crate::storage_dao_collection::{DaoCollection, DAO_COLLECTION, DaoState};
+use crate::storage_dao_collection::{AdminApprovalConfig, BlockNumber, DaoCollection, DAO_COLLECTION, DaoId, DaoRef, DaoState, DEFAULT_PENDING_OPERATION_TTL, FinalizationMode, GovernanceConfig, MembershipPolicy, Permission, ProposerPolicy, Quorum, Role};
 use crate::storage_dao_map::{ACTOR_DAO_MAP, ActorDaoMap};
-use crate::storage_proposal_map::{PROPOSAL_MAP, Proposal, ProposalMap, ProposalStatus};
+use crate::storage_membership::{MEMBERSHIP, hash_invite_secret, Invite, InviteHash, InviteInfo, Membership, MembersPage};
+use crate::storage_pending_operations::{PENDING_OPERATIONS, AdminOperation, PendingOperation, PendingOperations};
+use crate::storage_proposal_map::{PROPOSAL_MAP, ActionResult, Ballot, DepositStatus, Proposal, ProposalAction, ProposalDeposit, ProposalMap, ProposalStatus, VoteOption};
+use crate::storage_treasury::{TREASURY, Treasury};
 
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, TypeInfo)]
 pub enum Event {
     DaoCreated {
+        id: DaoId,
         name: String,
         creator: ActorId,
         token_actor: ActorId,
-        creation_block: u64
+        creation_block: BlockNumber
+    },
+    AdminAdded {
+        dao_name: String,
+        admin: ActorId,
+    },
+    AdminRemoved {
+        dao_name: String,
+        admin: ActorId,
+    },
+    CreatorTransferred {
+        dao_name: String,
+        previous_creator: ActorId,
+        new_creator: ActorId,
+    },
+    GovernanceUpdated {
+        dao_name: String,
+    },
+    AdminApprovalUpdated {
+        dao_name: String,
+        threshold: u8,
+        ttl: u32,
+    },
+    AdminOperationProposed {
+        dao_name: String,
+        operation_id: u32,
+        proposer: ActorId,
+    },
+    AdminOperationConfirmed {
+        dao_name: String,
+        operation_id: u32,
+        admin: ActorId,
+    },
+    AdminOperationExecuted {
+        dao_name: String,
+        operation_id: u32,
+    },
+    MembershipRequested {
+        dao_name: String,
+        applicant: ActorId,
+    },
+    MemberJoined {
+        dao_name: String,
+        member: ActorId,
+    },
+    MemberLeft {
+        dao_name: String,
+        member: ActorId,
+    },
+    MemberKicked {
+        dao_name: String,
+        member: ActorId,
+    },
+    // Invite events carry the invite id only, never the secret or its hash
+    InviteCreated {
+        dao_name: String,
+        invite_id: u32,
+        invitee: ActorId,
+        expires_at: BlockNumber,
+        max_uses: u32,
+    },
+    InviteRevoked {
+        dao_name: String,
+        invite_id: u32,
+    },
+    InviteRedeemed {
+        dao_name: String,
+        invite_id: u32,
+        member: ActorId,
+    },
+    RoleGranted {
+        dao_name: String,
+        role: Role,
+        actor: ActorId,
+    },
+    RoleRevoked {
+        dao_name: String,
+        role: Role,
+        actor: ActorId,
+    },
+    ProposalDrafted {
+        dao_name: String,
+        proposal_id: u32,
+        creator: ActorId,
     },
-    AdminAdded { admin: ActorId },
     ProposalCreated {
         dao_name: String,
         proposal_id: u32,
         creator: ActorId,
+        voting_start: BlockNumber,
+        voting_end: BlockNumber,
     },
     ProposalVoted {
         dao_name: String,
         proposal_id: u32,
         voter: ActorId,
-        vote_for: bool,
+        vote: VoteOption,
+        weight: U256,
+    },
+    VoteChanged {
+        dao_name: String,
+        proposal_id: u32,
+        voter: ActorId,
+        vote: VoteOption,
+        weight: U256,
+    },
+    VoteRetracted {
+        dao_name: String,
+        proposal_id: u32,
+        voter: ActorId,
     },
     ProposalFinalized {
         dao_name: String,
         proposal_id: u32,
         status: ProposalStatus,
     },
+    ProposalQueued {
+        dao_name: String,
+        proposal_id: u32,
+        eta_block: BlockNumber,
+    },
+    ProposalCancelled {
+        dao_name: String,
+        proposal_id: u32,
+        reason: String,
+    },
+    ProposalExecuted {
+        dao_name: String,
+        proposal_id: u32,
+        status: ProposalStatus,
+    },
+    TreasuryDeposited {
+        dao_name: String,
+        from: ActorId,
+        amount: U256,
+    },
+    TreasuryWithdrawn {
+        dao_name: String,
+        to: ActorId,
+        amount: U256,
+    },
+    ValueDeposited {
+        dao_name: String,
+        from: ActorId,
+        amount: u128,
+    },
+    ValueWithdrawn {
+        dao_name: String,
+        to: ActorId,
+        amount: u128,
+    },
+    DepositSlashed {
+        dao_name: String,
+        proposal_id: u32,
+        amount: U256,
+    },
+    DepositClaimed {
+        dao_name: String,
+        proposal_id: u32,
+        to: ActorId,
+        amount: U256,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, TypeInfo)]
 pub enum Error {
     NotInVotingPeriod,
     VoteNotEnded,
+    NoVotingPower,
+    AlreadyVoted,
+    NotVoted,
+    ProposalNotQueued,
+    TimelockNotExpired,
+    NotGuardian,
+    TokenTransferFailed,
+    NoValueAttached,
+    DaoAlreadyExists,
+    InvalidDaoName,
+    ReservedDaoName,
+    InvalidGovernanceConfig,
+    DaoNotFound,
+    ProposalNotFound,
+    Unauthorized,
+    InvalidVotingWindow,
+    AlreadyAdmin,
+    NotAdmin,
+    RoleAlreadyGranted,
+    TokenCallFailed,
+    ProposerNotAllowed,
+    InsufficientProposerBalance { required: U256, actual: U256 },
+    DepositNotRefundable,
+    InvalidStatusTransition,
+    AlreadyFinalized,
+    ProposalExpired,
+    VotingDelayTooLong,
+    CannotRemoveCreator,
+    RoleNotHeld,
+    InvalidApprovalConfig,
+    ThresholdExceedsAdmins,
+    OperationNotFound,
+    OperationExpired,
+    AlreadyApproved,
+    InsufficientTreasury,
+    AlreadyMember,
+    NotMember,
+    AlreadyApplied,
+    NoPendingApplication,
+    InviteOnly,
+    InvalidInvite,
+    InviteAlreadyExists,
+    InviteNotFound,
+    InviteExpired,
+    ProtectedDestination,
+    RoleRequiresProposal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+enum DaoRelationship {
+    Creator,
+    Member,
+}
+
+// A DAO an actor is involved in and how
+#[derive(Debug, Encode, Decode, TypeInfo)]
+#[codec(crate = sails_rs::scale_codec)]
+#[scale_info(crate = sails_rs::scale_info)]
+struct ActorDao {
+    id: DaoId,
+    name: String,
+    relationships: Vec<DaoRelationship>,
 }
 
 #[derive(Debug, Encode, Decode, TypeInfo)]
 #[codec(crate = sails_rs::scale_codec)]
 #[scale_info(crate = sails_rs::scale_info)]
 struct ResultDaoInfo<T> {
+    id: DaoId,
     name: String,
     description: String,
     token_actor: ActorId,
@@ -70,6 +287,47 @@ struct ResultTokenInfo {
     total_supply: U256,
 }
 
+// When a proposal's vote runs, relative to the block it is admitted in or fixed up front
+enum VotingWindow {
+    Relative { delay: u32, period: u32 },
+    Absolute { start: BlockNumber, end: BlockNumber },
+}
+
+impl VotingWindow {
+    // Delay and period as seen from `block`, the terms the DAO's limits are set in
+    fn delay_and_period(&self, block: BlockNumber) -> Result<(u32, u32), Error> {
+        match *self {
+            VotingWindow::Relative { delay, period } => Ok((delay, period)),
+            VotingWindow::Absolute { start, end } => {
+                let delay = start.checked_sub(block).ok_or(Error::InvalidVotingWindow)?;
+                let period = end.checked_sub(start).ok_or(Error::InvalidVotingWindow)?;
+                Ok((delay, period))
+            }
+        }
+    }
+
+    // Start and exclusive end of the vote for a proposal admitted at `block`
+    fn bounds(&self, block: BlockNumber) -> (BlockNumber, BlockNumber) {
+        match *self {
+            VotingWindow::Relative { delay, period } => {
+                let start = block.saturating_add(delay);
+                (start, start.saturating_add(period))
+            }
+            VotingWindow::Absolute { start, end } => (start, end),
+        }
+    }
+}
+
+// What a proposal settled and locked on its way out of Draft
+struct ProposalAdmission {
+    voting_start: BlockNumber,
+    voting_end: BlockNumber,
+    quorum: U256,
+    deposit: Option<ProposalDeposit>,
+    snapshot: HashMap<ActorId, U256>,
+    finalization: FinalizationMode,
+}
+
 struct NexusDaoService(());
 
 #[sails_rs::service(events = Event)]
@@ -84,6 +342,8 @@ impl NexusDaoService {
             if DAO_COLLECTION.is_none() {
                 DAO_COLLECTION = Some(DaoCollection {
                     daos: HashMap::new(),
+                    name_to_id: HashMap::new(),
+                    next_dao_id: 1,
                 });
             }
             if ACTOR_DAO_MAP.is_none() {
@@ -94,237 +354,1434 @@ impl NexusDaoService {
             if PROPOSAL_MAP.is_none() {
                 PROPOSAL_MAP = Some(ProposalMap {
                     dao_to_proposals: Default::default(),
+                    next_proposal_id: Default::default(),
+                    ballots: Default::default(),
+                    snapshots: Default::default(),
+                });
+            }
+            if TREASURY.is_none() {
+                TREASURY = Some(Treasury {
+                    vft_balances: HashMap::new(),
+                    value_balances: HashMap::new(),
+                });
+            }
+            if MEMBERSHIP.is_none() {
+                MEMBERSHIP = Some(Membership {
+                    members: HashMap::new(),
+                    applications: HashMap::new(),
+                    actor_to_daos: HashMap::new(),
+                    invites: HashMap::new(),
+                    next_invite_id: HashMap::new(),
+                });
+            }
+            if PENDING_OPERATIONS.is_none() {
+                PENDING_OPERATIONS = Some(PendingOperations {
+                    dao_to_operations: HashMap::new(),
+                    next_operation_id: HashMap::new(),
                 });
             }
         }
         Self(())
     }
 
-    pub fn create_dao(&mut self, name: String, description: String, token_actor: ActorId) -> bool {
+    pub fn create_dao(&mut self, name: String, description: String, token_actor: ActorId, governance: GovernanceConfig) -> Result<DaoId, Error> {
         let creator = msg::source();
-        let creation_block = exec::block_height().into();
+        let creation_block = exec::block_height();
 
+        if !governance.is_valid() {
+            return Err(Error::InvalidGovernanceConfig);
+        }
+
+        let normalized_name = DaoCollection::normalize_name(&name)?;
         let state = DaoCollection::get_mut();
+        if state.name_to_id.contains_key(&normalized_name) {
+            return Err(Error::DaoAlreadyExists);
+        }
+
+        let id = state.next_dao_id;
+        state.next_dao_id += 1;
+        state.name_to_id.insert(normalized_name, id);
         state.daos.insert(
-            name.clone(),
+            id,
             DaoState {
+                id,
+                name: name.clone(),
                 description,
                 token: token_actor,
-                admins: vec![creator],
+                roles: Role::ALL.iter().map(|role| (*role, vec![creator])).collect(),
                 creator,            // Set the creator
                 creation_block,     // Set the creation block number
+                governance,
+                admin_approval: AdminApprovalConfig {
+                    threshold: 1,
+                    ttl: DEFAULT_PENDING_OPERATION_TTL,
+                },
             },
         );
 
         let actor_map = ActorDaoMap::get_mut();
-        actor_map.actor_to_daos.entry(creator).or_insert_with(Vec::new).push(name.clone());
+        actor_map.actor_to_daos.entry(creator).or_insert_with(Vec::new).push(id);
+        Membership::get_mut().add_member(id, creator, creation_block);
 
         // Notify about DAO creation
         let _ = self.notify_on(Event::DaoCreated {
+            id,
             name,
             creator,
             token_actor,
             creation_block
         });
 
-        true
+        Ok(id)
+    }
+
+    // Voting opens `voting_delay` blocks after creation and stays open for `voting_period` blocks
+    pub async fn create_proposal(&mut self, dao: DaoRef, title: String, description: String, voting_delay: u32, voting_period: u32, actions: Vec<ProposalAction>) -> Result<u32, Error> {
+        let creator = msg::source();
+        let dao_id = DaoCollection::get().resolve(&dao).ok_or(Error::DaoNotFound)?;
+
+        let window = VotingWindow::Relative { delay: voting_delay, period: voting_period };
+        let admission = Self::admit_proposal(dao_id, creator, &actions, window).await?;
+        let proposal_id = Self::insert_draft(dao_id, creator, title, description, actions);
+        self.open_voting(dao_id, proposal_id, admission)?;
+
+        Ok(proposal_id)
+    }
+
+    // Voting is open from `voting_start` up to, but not including, `voting_end`
+    pub async fn create_proposal_at(&mut self, dao: DaoRef, title: String, description: String, voting_start: BlockNumber, voting_end: BlockNumber, actions: Vec<ProposalAction>) -> Result<u32, Error> {
+        let creator = msg::source();
+        let dao_id = DaoCollection::get().resolve(&dao).ok_or(Error::DaoNotFound)?;
+
+        let window = VotingWindow::Absolute { start: voting_start, end: voting_end };
+        let admission = Self::admit_proposal(dao_id, creator, &actions, window).await?;
+        let proposal_id = Self::insert_draft(dao_id, creator, title, description, actions);
+        self.open_voting(dao_id, proposal_id, admission)?;
+
+        Ok(proposal_id)
+    }
+
+    // Nothing is checked or locked until the creator submits the draft
+    pub fn create_draft(&mut self, dao: DaoRef, title: String, description: String, actions: Vec<ProposalAction>) -> Result<u32, Error> {
+        let creator = msg::source();
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let (dao_id, dao_name) = (dao.id, dao.name.clone());
+
+        let proposal_id = Self::insert_draft(dao_id, creator, title, description, actions);
+
+        let _ = self.notify_on(Event::ProposalDrafted {
+            dao_name,
+            proposal_id,
+            creator,
+        });
+
+        Ok(proposal_id)
     }
 
-    pub fn create_proposal(&mut self, dao_name: String, title: String, description: String, voting_start: u32, voting_end: u32) -> u32 {
+    // Move the caller's draft to Pending; voting opens `voting_delay` blocks from now
+    pub async fn submit_proposal(&mut self, dao: DaoRef, proposal_id: u32, voting_delay: u32, voting_period: u32) -> Result<(), Error> {
         let creator = msg::source();
-        let proposals = ProposalMap::get_mut().dao_to_proposals.entry(dao_name.clone()).or_insert_with(Vec::new);
-        let proposal_id = proposals.len() as u32 + 1;
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let (dao_id, token) = (dao.id, dao.token);
+
+        let proposal = ProposalMap::get().proposal(dao_id, proposal_id)?;
+        if proposal.creator != creator {
+            return Err(Error::Unauthorized);
+        }
+        if proposal.status != ProposalStatus::Draft {
+            return Err(Error::InvalidStatusTransition);
+        }
+        let actions = proposal.actions.clone();
 
-        if voting_start >= voting_end {
-            panic!("Voting start must be before voting end")
+        let window = VotingWindow::Relative { delay: voting_delay, period: voting_period };
+        let admission = Self::admit_proposal(dao_id, creator, &actions, window).await?;
+
+        // The draft may have been cancelled, or submitted by a second call, while the token calls were in flight
+        let still_draft = ProposalMap::get()
+            .proposal(dao_id, proposal_id)
+            .map_or(false, |proposal| proposal.status == ProposalStatus::Draft);
+        if !still_draft {
+            if let Some(deposit) = admission.deposit {
+                vft_calls::transfer(token, creator, deposit.amount).await?;
+            }
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        self.open_voting(dao_id, proposal_id, admission)
+    }
+
+    // Run the checks a proposal must pass before its vote opens and take what it locks
+    async fn admit_proposal(dao_id: DaoId, creator: ActorId, actions: &[ProposalAction], window: VotingWindow) -> Result<ProposalAdmission, Error> {
+        let dao = DaoCollection::get().daos.get(&dao_id).ok_or(Error::DaoNotFound)?;
+        // Copied out, since other messages may change the DAO while the token calls below are in flight
+        let token = dao.token;
+        let governance = dao.governance.clone();
+
+        let (voting_delay, voting_period) = window.delay_and_period(exec::block_height())?;
+        if voting_delay > governance.max_voting_delay {
+            return Err(Error::VotingDelayTooLong);
+        }
+        if voting_period < governance.min_voting_period || voting_period > governance.max_voting_period {
+            return Err(Error::InvalidVotingWindow);
+        }
+
+        let moves_funds = actions.iter().any(|action| match action {
+            ProposalAction::TreasuryTransfer { .. } | ProposalAction::ValueTransfer { .. } => true,
+            ProposalAction::SendMessage { value, .. } => *value != 0,
+            _ => false,
+        });
+        if moves_funds && !dao.is_allowed(&creator, Permission::ManageTreasury) {
+            return Err(Error::ProposerNotAllowed);
+        }
+        let sends_to_protected = actions.iter().any(|action| {
+            matches!(action, ProposalAction::SendMessage { destination, .. } if Self::is_protected_destination(destination))
+        });
+        if sends_to_protected {
+            return Err(Error::ProtectedDestination);
+        }
+
+        let allowed = match governance.proposer_policy {
+            ProposerPolicy::Anyone => true,
+            ProposerPolicy::Members => Membership::get().is_member(dao_id, &creator),
+            ProposerPolicy::Proposers => dao.is_allowed(&creator, Permission::CreateProposal),
+        };
+        if !allowed {
+            return Err(Error::ProposerNotAllowed);
+        }
+
+        let balance = if !governance.proposal_threshold.is_zero() {
+            vft_calls::balance_of(token, creator).await?
+        } else {
+            U256::zero()
+        };
+        if balance < governance.proposal_threshold {
+            return Err(Error::InsufficientProposerBalance {
+                required: governance.proposal_threshold,
+                actual: balance,
+            });
         }
 
-        proposals.push(Proposal {
+        let quorum = match governance.quorum {
+            Quorum::Absolute(amount) => amount,
+            Quorum::PercentOfSupply(percent) => {
+                let total_supply = vft_calls::total_supply(token).await?;
+                // Divided first so a supply near U256::MAX cannot overflow; the remainder keeps it exact
+                let (percent, hundred) = (U256::from(percent), U256::from(100));
+                total_supply / hundred * percent + total_supply % hundred * percent / hundred
+            }
+        };
+
+        // Voting power is fixed here, so tokens moved to another account later carry no second vote.
+        // The program's own holdings are deposits and treasury funds, which never vote.
+        let snapshot: HashMap<ActorId, U256> = vft_calls::balances(token)
+            .await?
+            .into_iter()
+            .filter(|(holder, balance)| *holder != exec::program_id() && !balance.is_zero())
+            .collect();
+
+        // Requires an allowance for the program on the DAO's token
+        let deposit_amount = governance.proposal_deposit;
+        let deposit = if deposit_amount.is_zero() {
+            None
+        } else {
+            if !vft_calls::transfer_from(token, creator, exec::program_id(), deposit_amount).await? {
+                return Err(Error::TokenTransferFailed);
+            }
+            Some(ProposalDeposit {
+                amount: deposit_amount,
+                status: DepositStatus::Locked,
+            })
+        };
+
+        // Taken after the token calls above, which may have spanned several blocks
+        let (voting_start, voting_end) = window.bounds(exec::block_height());
+
+        Ok(ProposalAdmission {
+            voting_start,
+            voting_end,
+            quorum,
+            deposit,
+            snapshot,
+            finalization: governance.finalization,
+        })
+    }
+
+    // Store a proposal as a Draft under the next free id
+    fn insert_draft(dao_id: DaoId, creator: ActorId, title: String, description: String, actions: Vec<ProposalAction>) -> u32 {
+        let proposal_map = ProposalMap::get_mut();
+        let proposal_id = proposal_map.allocate_id(dao_id);
+
+        proposal_map.dao_to_proposals.entry(dao_id).or_default().insert(proposal_id, Proposal {
+            id: proposal_id,
             title,
             description,
             creator,
-            voting_start,
-            voting_end,
-            status: ProposalStatus::Active,
-            votes_for: 0,
-            votes_against: 0,
+            voting_start: 0,
+            voting_end: 0,
+            quorum: U256::zero(),
+            status: ProposalStatus::Draft,
+            votes_for: U256::zero(),
+            votes_against: U256::zero(),
+            votes_abstain: U256::zero(),
+            votes_veto: U256::zero(),
+            actions,
+            execution_results: Vec::new(),
+            deposit: None,
+            deadline_scheduled: false,
         });
 
+        proposal_id
+    }
+
+    // Move an admitted draft to Pending and schedule its deadline
+    fn open_voting(&mut self, dao_id: DaoId, proposal_id: u32, admission: ProposalAdmission) -> Result<(), Error> {
+        let dao_name = DaoCollection::get().daos.get(&dao_id).ok_or(Error::DaoNotFound)?.name.clone();
+        let ProposalAdmission { voting_start, voting_end, quorum, deposit, snapshot, finalization } = admission;
+
+        let proposal_map = ProposalMap::get_mut();
+        let proposal = proposal_map.proposal_mut(dao_id, proposal_id)?;
+        proposal.transition(ProposalStatus::Pending)?;
+
+        // Reserve for the later execution hop as well, since a deadline message cannot reserve its own full gas again
+        let deadline_scheduled = match finalization {
+            FinalizationMode::Manual => false,
+            FinalizationMode::Automatic => scheduler::schedule_deadline(dao_id, proposal_id, voting_end, scheduler::DEADLINE_GAS),
+            FinalizationMode::AutomaticWithExecution => scheduler::schedule_deadline(dao_id, proposal_id, voting_end, 2 * scheduler::DEADLINE_GAS),
+        };
+
+        proposal.voting_start = voting_start;
+        proposal.voting_end = voting_end;
+        proposal.quorum = quorum;
+        proposal.deposit = deposit;
+        proposal.deadline_scheduled = deadline_scheduled;
+        let creator = proposal.creator;
+        proposal_map.snapshots.insert((dao_id, proposal_id), snapshot);
+
         let _ = self.notify_on(Event::ProposalCreated {
             dao_name,
-            proposal_id: proposal_id.clone(),
+            proposal_id,
             creator,
+            voting_start,
+            voting_end,
         });
 
-        proposal_id
+        Ok(())
     }
 
-    pub fn vote_on_proposal(&mut self, dao_name: String, proposal_id: u32, vote_for: bool) -> Result<(), Error> {
+    pub fn vote_on_proposal(&mut self, dao: DaoRef, proposal_id: u32, vote: VoteOption) -> Result<(), Error> {
         let voter = msg::source();
-        let proposals = ProposalMap::get_mut().dao_to_proposals.get_mut(&dao_name).expect("DAO not found");
-        let proposal = proposals.get_mut(proposal_id as usize - 1).expect("Proposal not found");
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let (dao_id, dao_name) = (dao.id, dao.name.clone());
+        let proposal_map = ProposalMap::get_mut();
 
-        if exec::block_height() < proposal.voting_start || exec::block_height() > proposal.voting_end {
+        // Voting power is the voter's balance of the DAO's governance token when the proposal was created
+        let weight = proposal_map.voting_power(dao_id, proposal_id, &voter);
+        let proposal = proposal_map.dao_to_proposals
+            .get_mut(&dao_id)
+            .and_then(|proposals| proposals.get_mut(&proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
+
+        if !proposal.is_voting_open(exec::block_height()) {
             return Err(Error::NotInVotingPeriod);
         }
 
-        if vote_for {
-            proposal.votes_for += 1;
-        } else {
-            proposal.votes_against += 1;
+        let ballots = proposal_map.ballots.entry((dao_id, proposal_id)).or_insert_with(HashMap::new);
+        if ballots.contains_key(&voter) {
+            return Err(Error::AlreadyVoted);
         }
+        if weight.is_zero() {
+            return Err(Error::NoVotingPower);
+        }
+
+        let ballot = Ballot { option: vote, weight };
+        proposal.add_ballot(&ballot);
+        ballots.insert(voter, ballot);
 
         let _ = self.notify_on(Event::ProposalVoted {
             dao_name,
             proposal_id,
             voter,
-            vote_for,
+            vote,
+            weight,
         });
 
         Ok(())
     }
 
-    pub fn finalize_proposal(&mut self, dao_name: String, proposal_id: u32) -> Result<(), Error> {
-
-        let proposals = ProposalMap::get_mut().dao_to_proposals.get_mut(&dao_name).expect("DAO not found");
-        let proposal = proposals.get_mut(proposal_id as usize - 1).expect("Proposal not found");
+    pub fn change_vote(&mut self, dao: DaoRef, proposal_id: u32, vote: VoteOption) -> Result<(), Error> {
+        let voter = msg::source();
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let (dao_id, dao_name) = (dao.id, dao.name.clone());
+        let proposal_map = ProposalMap::get_mut();
+        let proposal = proposal_map.dao_to_proposals
+            .get_mut(&dao_id)
+            .and_then(|proposals| proposals.get_mut(&proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
 
-        if exec::block_height() < proposal.voting_end {
-            return Err(Error::VoteNotEnded)
+        if !proposal.is_voting_open(exec::block_height()) {
+            return Err(Error::NotInVotingPeriod);
         }
 
-        if proposal.votes_for > proposal.votes_against {
-            proposal.status = ProposalStatus::Passed;
-        } else {
-            proposal.status = ProposalStatus::Rejected;
-        }
+        let ballot = proposal_map.ballots
+            .get_mut(&(dao_id, proposal_id))
+            .and_then(|ballots| ballots.get_mut(&voter))
+            .ok_or(Error::NotVoted)?;
 
-        let _ = self.notify_on(Event::ProposalFinalized {
+        // Move the ballot's weight from the old side to the new one
+        proposal.remove_ballot(ballot);
+        ballot.option = vote;
+        proposal.add_ballot(ballot);
+
+        let _ = self.notify_on(Event::VoteChanged {
             dao_name,
             proposal_id,
-            status: proposal.status.clone(),
+            voter,
+            vote,
+            weight: ballot.weight,
         });
 
         Ok(())
     }
 
-    pub fn get_proposals(&self, dao_name: String) -> Vec<Proposal> {
-        ProposalMap::get().dao_to_proposals.get(&dao_name).cloned().unwrap_or_default()
-    }
+    pub fn retract_vote(&mut self, dao: DaoRef, proposal_id: u32) -> Result<(), Error> {
+        let voter = msg::source();
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let (dao_id, dao_name) = (dao.id, dao.name.clone());
+        let proposal_map = ProposalMap::get_mut();
+        let proposal = proposal_map.dao_to_proposals
+            .get_mut(&dao_id)
+            .and_then(|proposals| proposals.get_mut(&proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
 
-    pub fn get_proposal(&self, dao_name: String, proposal_id: u32) -> Option<Proposal> {
-        ProposalMap::get().dao_to_proposals.get(&dao_name).and_then(|proposals| {
-            proposals.get(proposal_id as usize - 1).cloned()
-        })
-    }
+        if !proposal.is_voting_open(exec::block_height()) {
+            return Err(Error::NotInVotingPeriod);
+        }
 
-    pub async fn get_all_dao_info(&self) -> Vec<ResultDaoInfo<()>> {
-        let state = DaoCollection::get();
-        let mut result = Vec::new();
+        let ballot = proposal_map.ballots
+            .get_mut(&(dao_id, proposal_id))
+            .and_then(|ballots| ballots.remove(&voter))
+            .ok_or(Error::NotVoted)?;
 
-        for (name, dao) in state.daos.iter() {
-            result.push(ResultDaoInfo {
-                name: name.clone(),
-                description: dao.description.clone(),
-                token_actor: dao.token,
-                token: (),
-            });
-        }
-        result
+        proposal.remove_ballot(&ballot);
+
+        let _ = self.notify_on(Event::VoteRetracted {
+            dao_name,
+            proposal_id,
+            voter,
+        });
+
+        Ok(())
     }
 
+    pub fn finalize_proposal(&mut self, dao: DaoRef, proposal_id: u32) -> Result<(), Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let (dao_id, dao_name) = (dao.id, dao.name.clone());
+        let config = &dao.governance;
+        let block = exec::block_height();
 
-    pub async fn get_dao_info(&self, name: String) -> Option<ResultDaoInfo<ResultTokenInfo>> {
-        let state = DaoCollection::get();
+        let proposal = ProposalMap::get_mut().proposal_mut(dao_id, proposal_id)?;
+        if proposal.status == ProposalStatus::Draft {
+            return Err(Error::NotInVotingPeriod);
+        }
+        if !matches!(proposal.status, ProposalStatus::Pending | ProposalStatus::Active) {
+            return Err(Error::AlreadyFinalized);
+        }
+        if block < proposal.voting_end {
+            return Err(Error::VoteNotEnded);
+        }
 
-        if let Some(dao) = state.daos.get(&name) {
-            let token_info = {
-                // Query the token name
-                let call_payload = nexus_vft_client::nexus_vft::io::Name::encode_call();
-                let reply_bytes = gstd::msg::send_bytes_for_reply(dao.token, call_payload, 0, 0)
-                    .unwrap()
-                    .await
-                    .unwrap();
-                let token_name = <nexus_vft_client::nexus_vft::io::Name as sails_rs::calls::ActionIo>::decode_reply(&reply_bytes).unwrap();
+        proposal.refresh_status(block, config)?;
 
-                // Query the token symbol
-                let call_payload = nexus_vft_client::nexus_vft::io::Symbol::encode_call();
-                let reply_bytes = gstd::msg::send_bytes_for_reply(dao.token, call_payload, 0, 0)
-                    .unwrap()
-                    .await
-                    .unwrap();
-                let token_symbol = <nexus_vft_client::nexus_vft::io::Symbol as sails_rs::calls::ActionIo>::decode_reply(&reply_bytes).unwrap();
+        // Passing proposals wait out the DAO's execution delay before they can run
+        if proposal.status == ProposalStatus::Succeeded {
+            proposal.transition(ProposalStatus::Queued {
+                eta_block: block.saturating_add(config.execution_delay),
+            })?;
+        }
+        let slashed = proposal.settle_deposit();
 
-                // Query the token decimals
-                let call_payload = nexus_vft_client::nexus_vft::io::Decimals::encode_call();
-                let reply_bytes = gstd::msg::send_bytes_for_reply(dao.token, call_payload, 0, 0)
-                    .unwrap()
-                    .await
-                    .unwrap();
-                let token_decimals = <nexus_vft_client::nexus_vft::io::Decimals as sails_rs::calls::ActionIo>::decode_reply(&reply_bytes).unwrap();
+        let _ = self.notify_on(Event::ProposalFinalized {
+            dao_name: dao_name.clone(),
+            proposal_id,
+            status: proposal.status.clone(),
+        });
 
-                // Query the token total supply
-                let call_payload = nexus_vft_client::nexus_vft::io::TotalSupply::encode_call();
-                let reply_bytes = gstd::msg::send_bytes_for_reply(dao.token, call_payload, 0, 0)
-                    .unwrap()
-                    .await
-                    .unwrap();
-                let token_total_supply = <nexus_vft_client::nexus_vft::io::TotalSupply as sails_rs::calls::ActionIo>::decode_reply(&reply_bytes).unwrap();
-
-                ResultTokenInfo {
-                    name: token_name,
-                    symbol: token_symbol,
-                    decimals: token_decimals,
-                    total_supply: token_total_supply,
-                }
-            };
+        if let Some(amount) = slashed {
+            Treasury::get_mut().credit_vft(dao_id, amount);
+            let _ = self.notify_on(Event::DepositSlashed {
+                dao_name: dao_name.clone(),
+                proposal_id,
+                amount,
+            });
+        }
 
-            Some(ResultDaoInfo {
-                name: name,
-                description: dao.description.clone(),
-                token_actor: dao.token,
-                token: token_info,
-            })
-        } else {
-            None
+        if let ProposalStatus::Queued { eta_block } = proposal.status {
+            let _ = self.notify_on(Event::ProposalQueued {
+                dao_name,
+                proposal_id,
+                eta_block,
+            });
         }
-    }
 
-    pub fn get_daos_by_actor(&self, actor: ActorId) -> Option<Vec<String>> {
-        let actor_map = ActorDaoMap::get();
-        actor_map.actor_to_daos.get(&actor).cloned()
+        Ok(())
     }
 
-    pub fn add_admin(&mut self, dao_name: String, new_admin: ActorId) {
-        let state = DaoCollection::get_mut();
-        let dao = state.daos.get_mut(&dao_name).expect("DAO not found");
+    // Target of the delayed message scheduled when the proposal was created. If the reservation
+    // ran out the message never arrives and finalize_proposal / execute_proposal remain the manual path.
+    pub async fn process_deadline(&mut self, dao_id: DaoId, proposal_id: u32) -> Result<(), Error> {
+        if msg::source() != exec::program_id() {
+            return Err(Error::Unauthorized);
+        }
+        let dao = DaoCollection::get().daos.get(&dao_id).ok_or(Error::DaoNotFound)?;
 
-        let caller = msg::source();
-        if dao.admins.contains(&caller) {
-            if !dao.admins.contains(&new_admin) {
-                dao.admins.push(new_admin);
+        let proposal = ProposalMap::get_mut().proposal_mut(dao_id, proposal_id)?;
+        proposal.deadline_scheduled = false;
+
+        // It may have been finalized or cancelled by hand in the meantime
+        if matches!(proposal.status, ProposalStatus::Pending | ProposalStatus::Active) {
+            self.finalize_proposal(DaoRef::Id(dao_id), proposal_id)?;
+        }
+        if dao.governance.finalization != FinalizationMode::AutomaticWithExecution {
+            return Ok(());
+        }
 
-                // Notify about the admin being added
-                let _ = self.notify_on(Event::AdminAdded { admin: new_admin });
-            } else {
-                panic!("The user is already an administrator");
+        let proposal = ProposalMap::get_mut().proposal_mut(dao_id, proposal_id)?;
+        match proposal.status {
+            ProposalStatus::Queued { eta_block } if exec::block_height() >= eta_block => {
+                self.execute_proposal(DaoRef::Id(dao_id), proposal_id).await
             }
-        } else {
-            panic!("Only administrators can add new administrators");
+            ProposalStatus::Queued { eta_block } => {
+                proposal.deadline_scheduled = scheduler::schedule_deadline(dao_id, proposal_id, eta_block, scheduler::DEADLINE_GAS);
+                Ok(())
+            }
+            _ => Ok(()),
         }
     }
 
-    pub fn is_admin(&self, dao_name: String, user: ActorId) -> bool {
-        let state = DaoCollection::get();
-        let dao = state.daos.get(&dao_name).expect("DAO not found");
-        dao.admins.contains(&user)
-    }
+    pub async fn execute_proposal(&mut self, dao: DaoRef, proposal_id: u32) -> Result<(), Error> {
+        let caller = msg::source();
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        // The program itself executes through process_deadline
+        if caller != exec::program_id() && !dao.is_allowed(&caller, Permission::ExecuteProposal) {
+            return Err(Error::Unauthorized);
+        }
+        let (dao_id, dao_name, token) = (dao.id, dao.name.clone(), dao.token);
+        let block = exec::block_height();
+        let actions = {
+            let proposal = ProposalMap::get_mut().proposal_mut(dao_id, proposal_id)?;
 
-}
+            match proposal.effective_status(block, &dao.governance) {
+                ProposalStatus::Queued { eta_block } => {
+                    if block < eta_block {
+                        return Err(Error::TimelockNotExpired);
+                    }
+                }
+                ProposalStatus::Expired => {
+                    proposal.transition(ProposalStatus::Expired)?;
+                    return Err(Error::ProposalExpired);
+                }
+                _ => return Err(Error::ProposalNotQueued),
+            }
 
-pub struct NexusDaoProgram(());
+            // Mark as executed up front so a second call cannot run the actions again while replies are pending
+            proposal.transition(ProposalStatus::Executed)?;
+            proposal.actions.clone()
+        };
 
-#[sails_rs::program]
+        let mut results = Vec::new();
+        let mut status = ProposalStatus::Executed;
+
+        for action in actions {
+            match self.run_action(dao_id, &dao_name, token, action).await {
+                Ok(reply_bytes) => results.push(ActionResult::Success(reply_bytes)),
+                Err(reason) => {
+                    results.push(ActionResult::Failed(reason));
+                    status = ProposalStatus::ExecutionFailed;
+                    // Later actions may depend on this one, so stop here
+                    break;
+                }
+            }
+        }
+
+        let proposal = ProposalMap::get_mut().proposal_mut(dao_id, proposal_id)?;
+        proposal.execution_results = results;
+        if status != ProposalStatus::Executed {
+            proposal.transition(status.clone())?;
+        }
+
+        let _ = self.notify_on(Event::ProposalExecuted {
+            dao_name,
+            proposal_id,
+            status,
+        });
+
+        Ok(())
+    }
+
+    async fn run_action(&mut self, dao_id: DaoId, dao_name: &str, token: ActorId, action: ProposalAction) -> Result<Vec<u8>, String> {
+        match action {
+            ProposalAction::SendMessage { destination, payload, value } => {
+                if Self::is_protected_destination(&destination) {
+                    return Err("Protected destination".to_string());
+                }
+                // Attached value comes out of the DAO's own share of the program balance
+                if !Treasury::get_mut().debit_value(dao_id, value) {
+                    return Err("Insufficient value balance".to_string());
+                }
+                let reply = match msg::send_bytes_for_reply(destination, payload, value, 0) {
+                    Ok(future) => future.await,
+                    Err(err) => Err(err),
+                };
+                // A message that failed hands its value back to the program
+                if reply.is_err() {
+                    Treasury::get_mut().credit_value(dao_id, value);
+                }
+                reply.map_err(|err| format!("{:?}", err))
+            }
+            ProposalAction::TreasuryTransfer { to, amount } => {
+                // Debit before the transfer so the same funds cannot be spent twice while it is in flight
+                if !Treasury::get_mut().debit_vft(dao_id, amount) {
+                    return Err("Insufficient treasury balance".to_string());
+                }
+
+                match vft_calls::transfer(token, to, amount).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        Treasury::get_mut().credit_vft(dao_id, amount);
+                        return Err("Token transfer failed".to_string());
+                    }
+                    Err(err) => {
+                        Treasury::get_mut().credit_vft(dao_id, amount);
+                        return Err(format!("{:?}", err));
+                    }
+                }
+
+                let _ = self.notify_on(Event::TreasuryWithdrawn {
+                    dao_name: dao_name.to_string(),
+                    to,
+                    amount,
+                });
+
+                Ok(Vec::new())
+            }
+            ProposalAction::ValueTransfer { to, amount, gas_limit } => {
+                if !Treasury::get_mut().debit_value(dao_id, amount) {
+                    return Err("Insufficient value balance".to_string());
+                }
+
+                let reply = match msg::send_with_gas_for_reply(to, (), gas_limit, amount, 0) {
+                    Ok(future) => future.await,
+                    Err(err) => Err(err),
+                };
+                // A transfer the recipient rejected hands its value back to the program
+                if let Err(err) = reply {
+                    Treasury::get_mut().credit_value(dao_id, amount);
+                    return Err(format!("{:?}", err));
+                }
+
+                let _ = self.notify_on(Event::ValueWithdrawn {
+                    dao_name: dao_name.to_string(),
+                    to,
+                    amount,
+                });
+
+                Ok(Vec::new())
+            }
+            ProposalAction::UpdateGovernance(governance) => {
+                if !governance.is_valid() {
+                    return Err("Invalid governance config".to_string());
+                }
+                let dao = DaoCollection::get_mut().daos.get_mut(&dao_id).ok_or("DAO not found".to_string())?;
+                dao.governance = governance;
+
+                let _ = self.notify_on(Event::GovernanceUpdated {
+                    dao_name: dao_name.to_string(),
+                });
+
+                Ok(Vec::new())
+            }
+            ProposalAction::GrantRole { role, actor } => {
+                let dao = DaoCollection::get_mut().daos.get_mut(&dao_id).ok_or("DAO not found".to_string())?;
+                Self::check_admin_operation(dao, &AdminOperation::GrantRole { role, actor }).map_err(|err| format!("{:?}", err))?;
+                dao.grant_role(role, actor);
+
+                let _ = self.notify_on(Event::RoleGranted {
+                    dao_name: dao_name.to_string(),
+                    role,
+                    actor,
+                });
+
+                Ok(Vec::new())
+            }
+            ProposalAction::RevokeRole { role, actor } => {
+                let dao = DaoCollection::get_mut().daos.get_mut(&dao_id).ok_or("DAO not found".to_string())?;
+                Self::check_admin_operation(dao, &AdminOperation::RevokeRole { role, actor }).map_err(|err| format!("{:?}", err))?;
+                dao.revoke_role(role, &actor);
+
+                let _ = self.notify_on(Event::RoleRevoked {
+                    dao_name: dao_name.to_string(),
+                    role,
+                    actor,
+                });
+
+                Ok(Vec::new())
+            }
+            ProposalAction::KickMember(member) => {
+                if !Membership::get_mut().remove_member(dao_id, &member) {
+                    return Err("Not a member".to_string());
+                }
+
+                let _ = self.notify_on(Event::MemberKicked {
+                    dao_name: dao_name.to_string(),
+                    member,
+                });
+
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    // A message from the program to itself would pass the program-only checks in execute_proposal and process_deadline,
+    // and one to a DAO token could move tokens the program holds for every DAO past the treasury ledger
+    fn is_protected_destination(destination: &ActorId) -> bool {
+        *destination == exec::program_id() || DaoCollection::get().daos.values().any(|dao| dao.token == *destination)
+    }
+
+    // Run a sensitive admin operation right away, or park it until enough admins confirm it
+    async fn submit_admin_operation(&mut self, dao_id: DaoId, operation: AdminOperation) -> Result<(), Error> {
+        let proposer = msg::source();
+        let dao = DaoCollection::get().daos.get(&dao_id).ok_or(Error::DaoNotFound)?;
+        Self::check_admin_operation(dao, &operation)?;
+
+        if dao.admin_approval.threshold <= 1 {
+            return self.apply_admin_operation(dao_id, operation).await;
+        }
+
+        let block = exec::block_height();
+        let pending = PendingOperations::get_mut();
+        pending.prune_expired(dao_id, block);
+        let operation_id = pending.allocate_id(dao_id);
+        pending.dao_to_operations.entry(dao_id).or_default().insert(operation_id, PendingOperation {
+            id: operation_id,
+            operation,
+            proposer,
+            approvals: vec![proposer],
+            expires_at: block.saturating_add(dao.admin_approval.ttl),
+        });
+
+        let _ = self.notify_on(Event::AdminOperationProposed {
+            dao_name: dao.name.clone(),
+            operation_id,
+            proposer,
+        });
+
+        Ok(())
+    }
+
+    // Whether `operation` can be applied to the DAO in its current state
+    fn check_admin_operation(dao: &DaoState, operation: &AdminOperation) -> Result<(), Error> {
+        let admin_count = dao.roles.get(&Role::Admin).map_or(0, |admins| admins.len());
+        let can_lose_admin = |admin: &ActorId| {
+            // The creator is always an admin, so keeping them guarantees the DAO is never left without one
+            if *admin == dao.creator {
+                return Err(Error::CannotRemoveCreator);
+            }
+            if admin_count - 1 < dao.admin_approval.threshold as usize {
+                return Err(Error::ThresholdExceedsAdmins);
+            }
+            Ok(())
+        };
+
+        match operation {
+            AdminOperation::AddAdmin(admin) => {
+                if dao.has_role(Role::Admin, admin) {
+                    return Err(Error::AlreadyAdmin);
+                }
+            }
+            AdminOperation::RemoveAdmin(admin) => {
+                if !dao.has_role(Role::Admin, admin) {
+                    return Err(Error::NotAdmin);
+                }
+                can_lose_admin(admin)?;
+            }
+            AdminOperation::GrantRole { role, actor } => {
+                if dao.has_role(*role, actor) {
+                    return Err(Error::RoleAlreadyGranted);
+                }
+            }
+            AdminOperation::RevokeRole { role, actor } => {
+                if !dao.has_role(*role, actor) {
+                    return Err(Error::RoleNotHeld);
+                }
+                if *role == Role::Admin {
+                    can_lose_admin(actor)?;
+                }
+            }
+            AdminOperation::TransferCreator(_) => {}
+            AdminOperation::UpdateGovernance(governance) => {
+                if !governance.is_valid() {
+                    return Err(Error::InvalidGovernanceConfig);
+                }
+            }
+            AdminOperation::SetAdminApproval(config) => {
+                if config.threshold == 0 || config.threshold as usize > admin_count || config.ttl == 0 {
+                    return Err(Error::InvalidApprovalConfig);
+                }
+            }
+            AdminOperation::TreasuryTransfer { amount, .. } => {
+                if Treasury::get().vft_balance(dao.id) < *amount {
+                    return Err(Error::InsufficientTreasury);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn apply_admin_operation(&mut self, dao_id: DaoId, operation: AdminOperation) -> Result<(), Error> {
+        let dao = DaoCollection::get_mut().daos.get_mut(&dao_id).ok_or(Error::DaoNotFound)?;
+        // The DAO may have changed while the operation was waiting for approvals
+        Self::check_admin_operation(dao, &operation)?;
+        let dao_name = dao.name.clone();
+
+        match operation {
+            AdminOperation::AddAdmin(admin) => {
+                dao.grant_role(Role::Admin, admin);
+                let _ = self.notify_on(Event::AdminAdded { dao_name, admin });
+            }
+            AdminOperation::RemoveAdmin(admin) => {
+                dao.revoke_role(Role::Admin, &admin);
+                let _ = self.notify_on(Event::AdminRemoved { dao_name, admin });
+            }
+            AdminOperation::GrantRole { role, actor } => {
+                dao.grant_role(role, actor);
+                let _ = self.notify_on(Event::RoleGranted { dao_name, role, actor });
+            }
+            AdminOperation::RevokeRole { role, actor } => {
+                dao.revoke_role(role, &actor);
+                let _ = self.notify_on(Event::RoleRevoked { dao_name, role, actor });
+            }
+            AdminOperation::TransferCreator(new_creator) => {
+                let previous_creator = dao.creator;
+                dao.grant_role(Role::Admin, new_creator);
+                dao.creator = new_creator;
+
+                let actor_map = ActorDaoMap::get_mut();
+                if let Some(dao_ids) = actor_map.actor_to_daos.get_mut(&previous_creator) {
+                    dao_ids.retain(|id| *id != dao_id);
+                }
+                actor_map.actor_to_daos.entry(new_creator).or_insert_with(Vec::new).push(dao_id);
+
+                let _ = self.notify_on(Event::CreatorTransferred {
+                    dao_name,
+                    previous_creator,
+                    new_creator,
+                });
+            }
+            AdminOperation::UpdateGovernance(governance) => {
+                dao.governance = governance;
+                let _ = self.notify_on(Event::GovernanceUpdated { dao_name });
+            }
+            AdminOperation::SetAdminApproval(config) => {
+                let (threshold, ttl) = (config.threshold, config.ttl);
+                dao.admin_approval = config;
+                let _ = self.notify_on(Event::AdminApprovalUpdated { dao_name, threshold, ttl });
+            }
+            AdminOperation::TreasuryTransfer { to, amount } => {
+                let token = dao.token;
+                self.run_action(dao_id, &dao_name, token, ProposalAction::TreasuryTransfer { to, amount })
+                    .await
+                    .map_err(|_| Error::TokenTransferFailed)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn deposit_to_treasury(&mut self, dao: DaoRef, amount: U256) -> Result<(), Error> {
+        let from = msg::source();
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let (dao_id, dao_name, token) = (dao.id, dao.name.clone(), dao.token);
+
+        // Requires an allowance for the program on the DAO's token
+        if !vft_calls::transfer_from(token, from, exec::program_id(), amount).await? {
+            return Err(Error::TokenTransferFailed);
+        }
+        Treasury::get_mut().credit_vft(dao_id, amount);
+
+        let _ = self.notify_on(Event::TreasuryDeposited {
+            dao_name,
+            from,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub async fn claim_deposit(&mut self, dao: DaoRef, proposal_id: u32) -> Result<(), Error> {
+        let caller = msg::source();
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let (dao_id, dao_name, token) = (dao.id, dao.name.clone(), dao.token);
+
+        let proposal = ProposalMap::get_mut().proposal_mut(dao_id, proposal_id)?;
+        if proposal.creator != caller {
+            return Err(Error::Unauthorized);
+        }
+        let deposit = proposal
+            .deposit
+            .as_mut()
+            .filter(|deposit| deposit.status == DepositStatus::Refundable)
+            .ok_or(Error::DepositNotRefundable)?;
+
+        // Mark it claimed before the transfer so a second call cannot race it
+        deposit.status = DepositStatus::Claimed;
+        let amount = deposit.amount;
+
+        let transferred = vft_calls::transfer(token, caller, amount).await;
+        if transferred != Ok(true) {
+            if let Some(deposit) = ProposalMap::get_mut().proposal_mut(dao_id, proposal_id)?.deposit.as_mut() {
+                deposit.status = DepositStatus::Refundable;
+            }
+            return Err(transferred.err().unwrap_or(Error::TokenTransferFailed));
+        }
+
+        let _ = self.notify_on(Event::DepositClaimed {
+            dao_name,
+            proposal_id,
+            to: caller,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn get_treasury(&self, dao: DaoRef) -> Result<U256, Error> {
+        let dao_id = DaoCollection::get().resolve(&dao).ok_or(Error::DaoNotFound)?;
+        Ok(Treasury::get().vft_balance(dao_id))
+    }
+
+    pub fn deposit_value(&mut self, dao: DaoRef) -> Result<(), Error> {
+        let from = msg::source();
+        let amount = msg::value();
+
+        if amount == 0 {
+            return Err(Error::NoValueAttached);
+        }
+        // Trap rather than reply with an error, so the attached value goes back to the sender
+        let Some(dao) = DaoCollection::get().find(&dao) else {
+            panic!("DAO not found");
+        };
+        let (dao_id, dao_name) = (dao.id, dao.name.clone());
+        Treasury::get_mut().credit_value(dao_id, amount);
+
+        let _ = self.notify_on(Event::ValueDeposited {
+            dao_name,
+            from,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn get_value_treasury(&self, dao: DaoRef) -> Result<u128, Error> {
+        let dao_id = DaoCollection::get().resolve(&dao).ok_or(Error::DaoNotFound)?;
+        Ok(Treasury::get().value_balance(dao_id))
+    }
+
+    // The creator may withdraw a draft or a proposal until voting starts, admins until it is executed
+    pub fn cancel_proposal(&mut self, dao: DaoRef, proposal_id: u32, reason: String) -> Result<(), Error> {
+        let caller = msg::source();
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let (dao_id, dao_name) = (dao.id, dao.name.clone());
+
+        let proposal = ProposalMap::get_mut().proposal_mut(dao_id, proposal_id)?;
+        let status = proposal.effective_status(exec::block_height(), &dao.governance);
+
+        let can_cancel_any = dao.is_allowed(&caller, Permission::CancelProposal);
+        let is_creator_before_start = proposal.creator == caller && matches!(status, ProposalStatus::Draft | ProposalStatus::Pending);
+        if !can_cancel_any && !is_creator_before_start {
+            return Err(Error::Unauthorized);
+        }
+        if !status.can_transition_to(&ProposalStatus::Cancelled) {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        proposal.transition(ProposalStatus::Cancelled)?;
+        proposal.release_deposit();
+
+        let _ = self.notify_on(Event::ProposalCancelled {
+            dao_name,
+            proposal_id,
+            reason,
+        });
+
+        Ok(())
+    }
+
+    pub fn cancel_queued_proposal(&mut self, dao: DaoRef, proposal_id: u32) -> Result<(), Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let (dao_id, dao_name) = (dao.id, dao.name.clone());
+        if !dao.is_allowed(&msg::source(), Permission::CancelQueuedProposal) {
+            return Err(Error::NotGuardian);
+        }
+
+        let proposal = ProposalMap::get_mut().proposal_mut(dao_id, proposal_id)?;
+        if !matches!(proposal.effective_status(exec::block_height(), &dao.governance), ProposalStatus::Queued { .. }) {
+            return Err(Error::ProposalNotQueued);
+        }
+        proposal.transition(ProposalStatus::Cancelled)?;
+
+        let _ = self.notify_on(Event::ProposalCancelled {
+            dao_name,
+            proposal_id,
+            reason: "Cancelled by guardian".to_string(),
+        });
+
+        Ok(())
+    }
+
+    // Proposals are returned with their status as of the current block
+    pub fn get_proposals(&self, dao: DaoRef) -> Result<Vec<Proposal>, Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let block = exec::block_height();
+
+        Ok(ProposalMap::get().dao_to_proposals
+            .get(&dao.id)
+            .map(|proposals| {
+                proposals
+                    .values()
+                    .map(|proposal| Proposal {
+                        status: proposal.effective_status(block, &dao.governance),
+                        ..proposal.clone()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    pub fn get_proposal(&self, dao: DaoRef, proposal_id: u32) -> Result<Proposal, Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let proposal = ProposalMap::get().proposal(dao.id, proposal_id)?;
+
+        Ok(Proposal {
+            status: proposal.effective_status(exec::block_height(), &dao.governance),
+            ..proposal.clone()
+        })
+    }
+
+    pub fn get_governance_config(&self, dao: DaoRef) -> Result<GovernanceConfig, Error> {
+        DaoCollection::get()
+            .find(&dao)
+            .map(|dao| dao.governance.clone())
+            .ok_or(Error::DaoNotFound)
+    }
+
+    pub fn get_vote(&self, dao: DaoRef, proposal_id: u32, voter: ActorId) -> Result<Option<Ballot>, Error> {
+        let dao_id = DaoCollection::get().resolve(&dao).ok_or(Error::DaoNotFound)?;
+        ProposalMap::get().proposal(dao_id, proposal_id)?;
+
+        Ok(ProposalMap::get().ballots
+            .get(&(dao_id, proposal_id))
+            .and_then(|ballots| ballots.get(&voter).cloned()))
+    }
+
+    pub async fn get_all_dao_info(&self) -> Vec<ResultDaoInfo<()>> {
+        let state = DaoCollection::get();
+        let mut result = Vec::new();
+
+        for dao in state.daos.values() {
+            result.push(ResultDaoInfo {
+                id: dao.id,
+                name: dao.name.clone(),
+                description: dao.description.clone(),
+                token_actor: dao.token,
+                token: (),
+            });
+        }
+        result
+    }
+
+
+    pub async fn get_dao_info(&self, dao: DaoRef) -> Result<ResultDaoInfo<ResultTokenInfo>, Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        // Copied out, since other messages may change the DAO while the token calls below are in flight
+        let (id, name, description, token) = (dao.id, dao.name.clone(), dao.description.clone(), dao.token);
+
+        let token_info = ResultTokenInfo {
+            name: vft_calls::name(token).await?,
+            symbol: vft_calls::symbol(token).await?,
+            decimals: vft_calls::decimals(token).await?,
+            total_supply: vft_calls::total_supply(token).await?,
+        };
+
+        Ok(ResultDaoInfo {
+            id,
+            name,
+            description,
+            token_actor: token,
+            token: token_info,
+        })
+    }
+
+    // DAOs the actor created or is a member of, in order of DAO id
+    pub fn get_daos_by_actor(&self, actor: ActorId) -> Vec<ActorDao> {
+        let state = DaoCollection::get();
+        let created = ActorDaoMap::get().actor_to_daos.get(&actor);
+        let member_of = Membership::get().actor_to_daos.get(&actor);
+
+        let mut dao_ids: Vec<DaoId> = created.into_iter().flatten().chain(member_of.into_iter().flatten()).copied().collect();
+        dao_ids.sort_unstable();
+        dao_ids.dedup();
+
+        dao_ids
+            .into_iter()
+            .filter_map(|dao_id| {
+                let dao = state.daos.get(&dao_id)?;
+                let mut relationships = Vec::new();
+                if created.map_or(false, |ids| ids.contains(&dao_id)) {
+                    relationships.push(DaoRelationship::Creator);
+                }
+                if member_of.map_or(false, |ids| ids.contains(&dao_id)) {
+                    relationships.push(DaoRelationship::Member);
+                }
+                Some(ActorDao {
+                    id: dao_id,
+                    name: dao.name.clone(),
+                    relationships,
+                })
+            })
+            .collect()
+    }
+
+    // Open DAOs admit the caller at once; approval-required ones record an application
+    pub fn join_dao(&mut self, dao: DaoRef) -> Result<(), Error> {
+        let actor = msg::source();
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let membership = Membership::get_mut();
+
+        if membership.is_member(dao.id, &actor) {
+            return Err(Error::AlreadyMember);
+        }
+
+        match dao.governance.membership_policy {
+            MembershipPolicy::Open => {
+                membership.add_member(dao.id, actor, exec::block_height());
+                let _ = self.notify_on(Event::MemberJoined { dao_name: dao.name.clone(), member: actor });
+            }
+            MembershipPolicy::ApprovalRequired => {
+                if !membership.add_application(dao.id, actor) {
+                    return Err(Error::AlreadyApplied);
+                }
+                let _ = self.notify_on(Event::MembershipRequested { dao_name: dao.name.clone(), applicant: actor });
+            }
+            MembershipPolicy::InviteOnly => return Err(Error::InviteOnly),
+        }
+
+        Ok(())
+    }
+
+    // Also withdraws a pending application
+    pub fn leave_dao(&mut self, dao: DaoRef) -> Result<(), Error> {
+        let actor = msg::source();
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let membership = Membership::get_mut();
+
+        if membership.remove_member(dao.id, &actor) {
+            let _ = self.notify_on(Event::MemberLeft { dao_name: dao.name.clone(), member: actor });
+            return Ok(());
+        }
+        if membership.remove_application(dao.id, &actor) {
+            return Ok(());
+        }
+        Err(Error::NotMember)
+    }
+
+    // Admit an applicant, or anyone at all when the DAO is invite-only
+    pub fn approve_member(&mut self, dao: DaoRef, actor: ActorId) -> Result<(), Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        if !dao.is_allowed(&msg::source(), Permission::ManageMembers) {
+            return Err(Error::Unauthorized);
+        }
+
+        let membership = Membership::get_mut();
+        if membership.is_member(dao.id, &actor) {
+            return Err(Error::AlreadyMember);
+        }
+        let invite_only = dao.governance.membership_policy == MembershipPolicy::InviteOnly;
+        if !membership.has_applied(dao.id, &actor) && !invite_only {
+            return Err(Error::NoPendingApplication);
+        }
+        membership.add_member(dao.id, actor, exec::block_height());
+
+        let _ = self.notify_on(Event::MemberJoined { dao_name: dao.name.clone(), member: actor });
+
+        Ok(())
+    }
+
+    // Register an invite for `invitee` by the hash of a secret shared off-chain; redeemable up to `max_uses` times through `expires_at`
+    pub fn create_invite(&mut self, dao: DaoRef, invitee: ActorId, secret_hash: InviteHash, expires_at: BlockNumber, max_uses: u32) -> Result<u32, Error> {
+        let creator = msg::source();
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        if !dao.is_allowed(&creator, Permission::ManageMembers) {
+            return Err(Error::Unauthorized);
+        }
+
+        let block = exec::block_height();
+        if expires_at < block || max_uses == 0 {
+            return Err(Error::InvalidInvite);
+        }
+        let membership = Membership::get_mut();
+        if membership.has_live_invite(dao.id, &invitee, &secret_hash, block) {
+            return Err(Error::InviteAlreadyExists);
+        }
+
+        let invite_id = membership.allocate_invite_id(dao.id);
+        membership.add_invite(dao.id, Invite {
+            id: invite_id,
+            invitee,
+            secret_hash,
+            created_by: creator,
+            expires_at,
+            uses_left: max_uses,
+        });
+
+        let _ = self.notify_on(Event::InviteCreated {
+            dao_name: dao.name.clone(),
+            invite_id,
+            invitee,
+            expires_at,
+            max_uses,
+        });
+
+        Ok(invite_id)
+    }
+
+    pub fn revoke_invite(&mut self, dao: DaoRef, invite_id: u32) -> Result<(), Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        if !dao.is_allowed(&msg::source(), Permission::ManageMembers) {
+            return Err(Error::Unauthorized);
+        }
+
+        Membership::get_mut()
+            .remove_invite(dao.id, invite_id)
+            .ok_or(Error::InviteNotFound)?;
+
+        let _ = self.notify_on(Event::InviteRevoked {
+            dao_name: dao.name.clone(),
+            invite_id,
+        });
+
+        Ok(())
+    }
+
+    // Join with the secret of an invite issued to the caller, whatever the DAO's membership policy
+    pub fn join_with_invite(&mut self, dao: DaoRef, secret: Vec<u8>) -> Result<(), Error> {
+        let actor = msg::source();
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let membership = Membership::get_mut();
+
+        if membership.is_member(dao.id, &actor) {
+            return Err(Error::AlreadyMember);
+        }
+
+        let block = exec::block_height();
+        let invite_id = membership.redeem_invite(dao.id, &actor, &hash_invite_secret(&secret), block)?;
+        membership.add_member(dao.id, actor, block);
+
+        let _ = self.notify_on(Event::InviteRedeemed {
+            dao_name: dao.name.clone(),
+            invite_id,
+            member: actor,
+        });
+        let _ = self.notify_on(Event::MemberJoined { dao_name: dao.name.clone(), member: actor });
+
+        Ok(())
+    }
+
+    // Invites that can still be redeemed
+    pub fn get_invites(&self, dao: DaoRef) -> Result<Vec<InviteInfo>, Error> {
+        let dao_id = DaoCollection::get().resolve(&dao).ok_or(Error::DaoNotFound)?;
+        let block = exec::block_height();
+
+        Ok(Membership::get().invites
+            .get(&dao_id)
+            .map(|invites| {
+                invites
+                    .values()
+                    .filter(|invite| !invite.is_expired(block))
+                    .map(Invite::info)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    pub fn get_members(&self, dao: DaoRef, cursor: Option<ActorId>, limit: u32) -> Result<MembersPage, Error> {
+        let dao_id = DaoCollection::get().resolve(&dao).ok_or(Error::DaoNotFound)?;
+        Ok(Membership::get().page(dao_id, cursor, limit))
+    }
+
+    pub fn is_member(&self, dao: DaoRef, actor: ActorId) -> Result<bool, Error> {
+        let dao_id = DaoCollection::get().resolve(&dao).ok_or(Error::DaoNotFound)?;
+        Ok(Membership::get().is_member(dao_id, &actor))
+    }
+
+    pub async fn add_admin(&mut self, dao: DaoRef, new_admin: ActorId) -> Result<(), Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        if !dao.is_allowed(&msg::source(), Permission::ManageRoles) {
+            return Err(Error::Unauthorized);
+        }
+        self.submit_admin_operation(dao.id, AdminOperation::AddAdmin(new_admin)).await
+    }
+
+    pub async fn remove_admin(&mut self, dao: DaoRef, admin: ActorId) -> Result<(), Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        if !dao.is_allowed(&msg::source(), Permission::ManageRoles) {
+            return Err(Error::Unauthorized);
+        }
+        self.submit_admin_operation(dao.id, AdminOperation::RemoveAdmin(admin)).await
+    }
+
+    // The new creator is made an admin if they are not one already; the previous creator stays an admin
+    pub async fn transfer_creator(&mut self, dao: DaoRef, new_creator: ActorId) -> Result<(), Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        if dao.creator != msg::source() {
+            return Err(Error::Unauthorized);
+        }
+        self.submit_admin_operation(dao.id, AdminOperation::TransferCreator(new_creator)).await
+    }
+
+    // Privileged roles go through a proposal with a GrantRole action instead; admins can still revoke them
+    pub async fn grant_role(&mut self, dao: DaoRef, role: Role, actor: ActorId) -> Result<(), Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        if !dao.is_allowed(&msg::source(), Permission::ManageRoles) {
+            return Err(Error::Unauthorized);
+        }
+        if role.is_privileged() {
+            return Err(Error::RoleRequiresProposal);
+        }
+        self.submit_admin_operation(dao.id, AdminOperation::GrantRole { role, actor }).await
+    }
+
+    pub async fn revoke_role(&mut self, dao: DaoRef, role: Role, actor: ActorId) -> Result<(), Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        if !dao.is_allowed(&msg::source(), Permission::ManageRoles) {
+            return Err(Error::Unauthorized);
+        }
+        self.submit_admin_operation(dao.id, AdminOperation::RevokeRole { role, actor }).await
+    }
+
+    // Change the governance settings directly instead of through a proposal
+    pub async fn update_governance(&mut self, dao: DaoRef, governance: GovernanceConfig) -> Result<(), Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        if !dao.is_allowed(&msg::source(), Permission::ManageConfig) {
+            return Err(Error::Unauthorized);
+        }
+        self.submit_admin_operation(dao.id, AdminOperation::UpdateGovernance(governance)).await
+    }
+
+    pub async fn set_admin_approval(&mut self, dao: DaoRef, config: AdminApprovalConfig) -> Result<(), Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        if !dao.is_allowed(&msg::source(), Permission::ManageConfig) {
+            return Err(Error::Unauthorized);
+        }
+        self.submit_admin_operation(dao.id, AdminOperation::SetAdminApproval(config)).await
+    }
+
+    pub async fn treasury_transfer(&mut self, dao: DaoRef, to: ActorId, amount: U256) -> Result<(), Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        if !dao.is_allowed(&msg::source(), Permission::ManageTreasury) {
+            return Err(Error::Unauthorized);
+        }
+        self.submit_admin_operation(dao.id, AdminOperation::TreasuryTransfer { to, amount }).await
+    }
+
+    // Add the caller's approval; the operation runs as soon as enough current admins have approved it
+    pub async fn confirm_admin_operation(&mut self, dao: DaoRef, operation_id: u32) -> Result<(), Error> {
+        let admin = msg::source();
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        let (dao_id, dao_name) = (dao.id, dao.name.clone());
+        if !dao.has_role(Role::Admin, &admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        let block = exec::block_height();
+        let pending = PendingOperations::get_mut();
+        let expired = pending.operation_mut(dao_id, operation_id)?.is_expired(block);
+        pending.prune_expired(dao_id, block);
+        if expired {
+            return Err(Error::OperationExpired);
+        }
+        let operation = pending.operation_mut(dao_id, operation_id)?;
+        if operation.approvals.contains(&admin) {
+            return Err(Error::AlreadyApproved);
+        }
+        operation.approvals.push(admin);
+
+        let _ = self.notify_on(Event::AdminOperationConfirmed {
+            dao_name: dao_name.clone(),
+            operation_id,
+            admin,
+        });
+
+        // Approvals from actors who have since lost the Admin role no longer count
+        let approvals = operation.approvals.iter().filter(|approver| dao.has_role(Role::Admin, approver)).count();
+        if approvals < dao.admin_approval.threshold as usize {
+            return Ok(());
+        }
+
+        // An operation the DAO cannot take yet stays open with its approvals
+        Self::check_admin_operation(dao, &operation.operation)?;
+        // Taken out while it applies, so a second confirmation cannot run a treasury transfer again; put back if it fails
+        let operation = pending.remove(dao_id, operation_id).ok_or(Error::OperationNotFound)?;
+        if let Err(err) = self.apply_admin_operation(dao_id, operation.operation.clone()).await {
+            PendingOperations::get_mut().dao_to_operations.entry(dao_id).or_default().insert(operation_id, operation);
+            return Err(err);
+        }
+
+        let _ = self.notify_on(Event::AdminOperationExecuted {
+            dao_name,
+            operation_id,
+        });
+
+        Ok(())
+    }
+
+    // Operations still open for confirmation
+    pub fn get_pending_operations(&self, dao: DaoRef) -> Result<Vec<PendingOperation>, Error> {
+        let dao_id = DaoCollection::get().resolve(&dao).ok_or(Error::DaoNotFound)?;
+        let block = exec::block_height();
+
+        Ok(PendingOperations::get().dao_to_operations
+            .get(&dao_id)
+            .map(|operations| {
+                operations
+                    .values()
+                    .filter(|operation| !operation.is_expired(block))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    pub fn get_admin_approval(&self, dao: DaoRef) -> Result<AdminApprovalConfig, Error> {
+        DaoCollection::get()
+            .find(&dao)
+            .map(|dao| dao.admin_approval.clone())
+            .ok_or(Error::DaoNotFound)
+    }
+
+    pub fn has_role(&self, dao: DaoRef, role: Role, actor: ActorId) -> Result<bool, Error> {
+        let dao = DaoCollection::get().find(&dao).ok_or(Error::DaoNotFound)?;
+        Ok(dao.has_role(role, &actor))
+    }
+
+    pub fn is_admin(&self, dao: DaoRef, user: ActorId) -> Result<bool, Error> {
+        self.has_role(dao, Role::Admin, user)
+    }
+
+}
+
+pub struct NexusDaoProgram(());
+
+#[sails_rs::program]
 impl NexusDaoProgram {
     pub fn new() -> Self {
         NexusDaoService::init();
@@ -332,6 +1789,14 @@ impl NexusDaoProgram {
     }
 
     pub fn nexus_dao(&self) -> NexusDaoService {
+        // Only deposit_value books incoming value; anything attached to another call would belong to no DAO,
+        // so trap and let it be refunded
+        if msg::value() != 0 {
+            let deposit_route = ("NexusDao", "DepositValue").encode();
+            if !msg::load_bytes().map_or(false, |payload| payload.starts_with(&deposit_route)) {
+                panic!("Value is only accepted by DepositValue");
+            }
+        }
         NexusDaoService::new()
     }
 }
@@ -0,0 +1,27 @@
+#![no_std]
+
+use gstd::{exec, msg};
+use sails_rs::prelude::*;
+use crate::storage_dao_collection::{BlockNumber, DaoId};
+
+// Gas set aside for a single run of `process_deadline`
+pub(crate) const DEADLINE_GAS: u64 = 10_000_000_000;
+// Blocks the reservation outlives the delay by, so it is still there when the message is dispatched
+const RESERVATION_MARGIN: u32 = 10;
+
+// Have the program call its own `process_deadline` once `at_block` is reached, paid from `gas`
+// reserved out of the current message. Returns false when nothing could be scheduled, in which
+// case the proposal has to be finalized by hand.
+pub(crate) fn schedule_deadline(dao_id: DaoId, proposal_id: u32, at_block: BlockNumber, gas: u64) -> bool {
+    let delay = at_block.saturating_sub(exec::block_height()).max(1);
+    let Ok(reservation_id) = exec::reserve_gas(gas, delay.saturating_add(RESERVATION_MARGIN)) else {
+        return false;
+    };
+
+    let payload = ("NexusDao", "ProcessDeadline", dao_id, proposal_id).encode();
+    if msg::send_bytes_delayed_from_reservation(reservation_id, exec::program_id(), payload, 0, delay).is_err() {
+        let _ = reservation_id.unreserve();
+        return false;
+    }
+    true
+}
@@ -4,20 +4,248 @@ use gstd::collections::HashMap;
 use gstd::{exec, msg};
 use sails_rs::prelude::*;
 use vft_service::Service as VftService; // Import VFT standard service
+use crate::Error;
 
 pub(crate) static mut DAO_COLLECTION: Option<DaoCollection> = None;
 
+pub const MIN_DAO_NAME_LENGTH: usize = 3;
+pub const MAX_DAO_NAME_LENGTH: usize = 32;
+// Compared against the normalized form of a name
+pub const RESERVED_DAO_NAMES: [&str; 6] = ["admin", "dao", "nexus", "nexus-dao", "root", "system"];
+
+// Blocks a pending admin operation stays open for confirmation unless the DAO sets otherwise
+pub const DEFAULT_PENDING_OPERATION_TTL: u32 = 28_800;
+
+pub type DaoId = u64;
+// Block heights as returned by exec::block_height()
+pub type BlockNumber = u32;
+
+// Service methods accept a DAO either by its id or by its (case-insensitive) name
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum DaoRef {
+    Id(DaoId),
+    Name(String),
+}
+
 pub struct DaoState {
+    pub id: DaoId,
+    pub name: String,
     pub description: String,
     // pub token: VftService,
     pub token: ActorId, // Use ActorId instead of VftService to represent the token contract
-    pub admins: Vec<ActorId>,
+    // Holders of each role; the creator starts out with all of them
+    pub roles: HashMap<Role, Vec<ActorId>>,
     pub creator: ActorId,
-    pub creation_block: u64,
+    pub creation_block: BlockNumber,
+    pub governance: GovernanceConfig,
+    pub admin_approval: AdminApprovalConfig,
+}
+
+// M-of-N admin approval for sensitive admin operations
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct AdminApprovalConfig {
+    // Admin approvals an operation needs; 1 lets a single admin act alone
+    pub threshold: u8,
+    // Blocks a pending operation stays open for confirmation
+    pub ttl: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode, TypeInfo)]
+pub enum Role {
+    Admin,
+    Proposer,
+    Executor,
+    Guardian,
+    TreasuryManager,
+    MemberManager,
+}
+
+// Operations that are guarded by a role
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Permission {
+    ManageRoles,
+    // Change governance and admin approval settings directly
+    ManageConfig,
+    // Withdraw a proposal at any point before it is executed
+    CancelProposal,
+    CreateProposal,
+    // Move treasury funds, directly or through proposals
+    ManageTreasury,
+    ExecuteProposal,
+    // Stop a queued proposal during its timelock
+    CancelQueuedProposal,
+    ManageMembers,
+}
+
+impl Role {
+    pub const ALL: [Role; 6] = [
+        Role::Admin,
+        Role::Proposer,
+        Role::Executor,
+        Role::Guardian,
+        Role::TreasuryManager,
+        Role::MemberManager,
+    ];
+
+    // Roles that run, stop or fund proposals are only handed out by a vote, so an admin cannot grant them alone
+    pub fn is_privileged(&self) -> bool {
+        matches!(self, Role::Executor | Role::Guardian | Role::TreasuryManager)
+    }
+
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Role::Admin => &[Permission::ManageRoles, Permission::ManageConfig, Permission::CancelProposal],
+            Role::Proposer => &[Permission::CreateProposal],
+            Role::Executor => &[Permission::ExecuteProposal],
+            Role::Guardian => &[Permission::CancelQueuedProposal],
+            Role::TreasuryManager => &[Permission::ManageTreasury],
+            Role::MemberManager => &[Permission::ManageMembers],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct GovernanceConfig {
+    pub quorum: Quorum,
+    pub approval_threshold: ApprovalThreshold,
+    pub tie_break: TieBreak,
+    // Share of all cast votes (in percent) that NoWithVeto must exceed to veto a proposal
+    pub veto_threshold_percent: u8,
+    // Most blocks a proposal may wait between creation and the start of voting
+    pub max_voting_delay: u32,
+    // Allowed length of the voting period, in blocks
+    pub min_voting_period: u32,
+    pub max_voting_period: u32,
+    // Blocks a passed proposal waits in the queue before it can be executed
+    pub execution_delay: u32,
+    // Blocks a queued proposal stays executable once its delay is over; 0 means it never expires
+    pub execution_grace_period: u32,
+    pub proposer_policy: ProposerPolicy,
+    pub membership_policy: MembershipPolicy,
+    // Governance tokens a proposer must hold to create a proposal
+    pub proposal_threshold: U256,
+    // Governance tokens a proposer locks with each proposal, refunded unless it is vetoed or misses quorum
+    pub proposal_deposit: U256,
+    pub finalization: FinalizationMode,
+}
+
+// What happens to a proposal when its voting period ends
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum FinalizationMode {
+    // Someone has to call finalize_proposal
+    Manual,
+    // The program finalizes it through a delayed message to itself
+    Automatic,
+    // As Automatic, and a passed proposal is also executed once its delay is over
+    AutomaticWithExecution,
+}
+
+// How actors become members of the DAO
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum MembershipPolicy {
+    // join_dao admits the caller straight away
+    Open,
+    // join_dao files an application that a member manager approves
+    ApprovalRequired,
+    // Only member managers can admit new members
+    InviteOnly,
+}
+
+// Who may call create_proposal for the DAO
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum ProposerPolicy {
+    Anyone,
+    // Members of the DAO
+    Members,
+    // Holders of the Proposer role
+    Proposers,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum Quorum {
+    // Minimum number of tokens that must take part in the vote
+    Absolute(U256),
+    // Minimum participation as a percentage of the token total supply
+    PercentOfSupply(u8),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum ApprovalThreshold {
+    SimpleMajority,
+    TwoThirds,
+    Percent(u8),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum TieBreak {
+    Reject,
+    Pass,
+}
+
+impl GovernanceConfig {
+    pub fn is_valid(&self) -> bool {
+        let quorum_valid = match self.quorum {
+            Quorum::Absolute(_) => true,
+            Quorum::PercentOfSupply(percent) => percent <= 100,
+        };
+        let threshold_valid = match self.approval_threshold {
+            ApprovalThreshold::Percent(percent) => percent > 0 && percent <= 100,
+            _ => true,
+        };
+        let period_valid = self.min_voting_period > 0 && self.min_voting_period <= self.max_voting_period;
+        quorum_valid && threshold_valid && period_valid && self.veto_threshold_percent <= 100
+    }
+}
+
+impl ApprovalThreshold {
+    // Fraction (numerator, denominator) of decisive votes that must be For
+    pub fn ratio(&self) -> (u32, u32) {
+        match self {
+            ApprovalThreshold::SimpleMajority => (1, 2),
+            ApprovalThreshold::TwoThirds => (2, 3),
+            ApprovalThreshold::Percent(percent) => (*percent as u32, 100),
+        }
+    }
+}
+
+impl DaoState {
+    pub fn has_role(&self, role: Role, actor: &ActorId) -> bool {
+        self.roles.get(&role).map_or(false, |holders| holders.contains(actor))
+    }
+
+    // Whether any role the actor holds carries `permission`
+    pub fn is_allowed(&self, actor: &ActorId, permission: Permission) -> bool {
+        self.roles
+            .iter()
+            .any(|(role, holders)| role.permissions().contains(&permission) && holders.contains(actor))
+    }
+
+    // Returns false if the actor already held the role
+    pub fn grant_role(&mut self, role: Role, actor: ActorId) -> bool {
+        let holders = self.roles.entry(role).or_default();
+        if holders.contains(&actor) {
+            return false;
+        }
+        holders.push(actor);
+        true
+    }
+
+    // Returns false if the actor did not hold the role
+    pub fn revoke_role(&mut self, role: Role, actor: &ActorId) -> bool {
+        let Some(holders) = self.roles.get_mut(&role) else {
+            return false;
+        };
+        let held = holders.len();
+        holders.retain(|holder| holder != actor);
+        holders.len() != held
+    }
 }
 
 pub struct DaoCollection {
-    pub daos: HashMap<String, DaoState>,
+    pub daos: HashMap<DaoId, DaoState>,
+    // Normalized name to id, so lookalike names resolve to (and cannot shadow) the same DAO
+    pub name_to_id: HashMap<String, DaoId>,
+    pub next_dao_id: DaoId,
 }
 
 impl DaoCollection {
@@ -28,4 +256,39 @@ impl DaoCollection {
     pub fn get_mut() -> &'static mut Self {
         unsafe { DAO_COLLECTION.as_mut().expect("DAO collection is not initialized") }
     }
+
+    pub fn resolve(&self, dao: &DaoRef) -> Option<DaoId> {
+        match dao {
+            DaoRef::Id(id) => self.daos.contains_key(id).then_some(*id),
+            DaoRef::Name(name) => {
+                let normalized = Self::normalize_name(name).ok()?;
+                self.name_to_id.get(&normalized).copied()
+            }
+        }
+    }
+
+    pub fn find(&self, dao: &DaoRef) -> Option<&DaoState> {
+        self.resolve(dao).and_then(|id| self.daos.get(&id))
+    }
+
+    // Fold a DAO name into the form used for uniqueness checks: ASCII lowercase with '_' treated as '-'
+    pub fn normalize_name(name: &str) -> Result<String, Error> {
+        if name.len() < MIN_DAO_NAME_LENGTH || name.len() > MAX_DAO_NAME_LENGTH {
+            return Err(Error::InvalidDaoName);
+        }
+        if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(Error::InvalidDaoName);
+        }
+
+        let normalized: String = name
+            .chars()
+            .map(|c| if c == '_' { '-' } else { c.to_ascii_lowercase() })
+            .collect();
+
+        if RESERVED_DAO_NAMES.contains(&normalized.as_str()) {
+            return Err(Error::ReservedDaoName);
+        }
+
+        Ok(normalized)
+    }
 }
@@ -3,11 +3,12 @@
 use gstd::ActorId;
 use gstd::collections::HashMap;
 use sails_rs::prelude::*;
+use crate::storage_dao_collection::DaoId;
 
 pub(crate) static mut ACTOR_DAO_MAP: Option<ActorDaoMap> = None;
 
 pub struct ActorDaoMap {
-    pub(crate) actor_to_daos: HashMap<ActorId, Vec<String>>,
+    pub(crate) actor_to_daos: HashMap<ActorId, Vec<DaoId>>,
 }
 
 impl ActorDaoMap {
@@ -0,0 +1,207 @@
+#![no_std]
+
+use blake2::{Blake2b, Digest, digest::consts::U32};
+use core::ops::Bound;
+use gstd::ActorId;
+use gstd::collections::{BTreeMap, BTreeSet, HashMap};
+use sails_rs::prelude::*;
+use crate::storage_dao_collection::{BlockNumber, DaoId};
+use crate::Error;
+
+pub(crate) static mut MEMBERSHIP: Option<Membership> = None;
+
+// Largest page get_members hands out
+pub const MAX_MEMBERS_PAGE: u32 = 100;
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct Member {
+    pub actor: ActorId,
+    pub joined_block: BlockNumber,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct MembersPage {
+    pub members: Vec<Member>,
+    // Pass back as `cursor` to get the next page; None once the list is exhausted
+    pub next_cursor: Option<ActorId>,
+}
+
+// blake2b-256 of an invite secret; only the hash is ever stored
+pub type InviteHash = [u8; 32];
+
+pub fn hash_invite_secret(secret: &[u8]) -> InviteHash {
+    Blake2b::<U32>::digest(secret).into()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Invite {
+    pub id: u32,
+    // Only this actor can redeem the invite, so the secret revealed by join_with_invite is of no use to anyone else
+    pub invitee: ActorId,
+    pub secret_hash: InviteHash,
+    pub created_by: ActorId,
+    // Last block at which the invite can still be redeemed
+    pub expires_at: BlockNumber,
+    pub uses_left: u32,
+}
+
+// What get_invites shows of an invite; the secret hash is left out
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct InviteInfo {
+    pub id: u32,
+    pub invitee: ActorId,
+    pub created_by: ActorId,
+    pub expires_at: BlockNumber,
+    pub uses_left: u32,
+}
+
+impl Invite {
+    pub fn is_expired(&self, block: BlockNumber) -> bool {
+        block > self.expires_at
+    }
+
+    pub fn info(&self) -> InviteInfo {
+        InviteInfo {
+            id: self.id,
+            invitee: self.invitee,
+            created_by: self.created_by,
+            expires_at: self.expires_at,
+            uses_left: self.uses_left,
+        }
+    }
+}
+
+pub struct Membership {
+    // Members of each DAO, ordered by actor id so they can be paged through with a cursor
+    pub(crate) members: HashMap<DaoId, BTreeMap<ActorId, Member>>,
+    // Actors waiting for approval to join each DAO
+    pub(crate) applications: HashMap<DaoId, BTreeSet<ActorId>>,
+    // Reverse index of `members`
+    pub(crate) actor_to_daos: HashMap<ActorId, BTreeSet<DaoId>>,
+    // Outstanding invites of each DAO by id; spent and revoked ones are dropped
+    pub(crate) invites: HashMap<DaoId, BTreeMap<u32, Invite>>,
+    pub(crate) next_invite_id: HashMap<DaoId, u32>,
+}
+
+impl Membership {
+    pub fn get() -> &'static Self {
+        unsafe { MEMBERSHIP.as_ref().expect("Membership is not initialized") }
+    }
+
+    pub fn get_mut() -> &'static mut Self {
+        unsafe { MEMBERSHIP.as_mut().expect("Membership is not initialized") }
+    }
+
+    pub fn is_member(&self, dao_id: DaoId, actor: &ActorId) -> bool {
+        self.members.get(&dao_id).map_or(false, |members| members.contains_key(actor))
+    }
+
+    pub fn has_applied(&self, dao_id: DaoId, actor: &ActorId) -> bool {
+        self.applications.get(&dao_id).map_or(false, |applicants| applicants.contains(actor))
+    }
+
+    // Returns false if the actor was already a member; clears any pending application
+    pub fn add_member(&mut self, dao_id: DaoId, actor: ActorId, block: BlockNumber) -> bool {
+        self.remove_application(dao_id, &actor);
+
+        let members = self.members.entry(dao_id).or_default();
+        if members.contains_key(&actor) {
+            return false;
+        }
+        members.insert(actor, Member { actor, joined_block: block });
+        self.actor_to_daos.entry(actor).or_default().insert(dao_id);
+        true
+    }
+
+    // Returns false if the actor was not a member
+    pub fn remove_member(&mut self, dao_id: DaoId, actor: &ActorId) -> bool {
+        let removed = self
+            .members
+            .get_mut(&dao_id)
+            .map_or(false, |members| members.remove(actor).is_some());
+        if removed {
+            if let Some(dao_ids) = self.actor_to_daos.get_mut(actor) {
+                dao_ids.remove(&dao_id);
+            }
+        }
+        removed
+    }
+
+    // Returns false if the actor had already applied
+    pub fn add_application(&mut self, dao_id: DaoId, actor: ActorId) -> bool {
+        self.applications.entry(dao_id).or_default().insert(actor)
+    }
+
+    pub fn remove_application(&mut self, dao_id: DaoId, actor: &ActorId) -> bool {
+        self.applications
+            .get_mut(&dao_id)
+            .map_or(false, |applicants| applicants.remove(actor))
+    }
+
+    // Up to `limit` members that come after `cursor`
+    pub fn page(&self, dao_id: DaoId, cursor: Option<ActorId>, limit: u32) -> MembersPage {
+        let limit = limit.clamp(1, MAX_MEMBERS_PAGE) as usize;
+        let Some(members) = self.members.get(&dao_id) else {
+            return MembersPage { members: Vec::new(), next_cursor: None };
+        };
+
+        let start = cursor.map_or(Bound::Unbounded, Bound::Excluded);
+        let mut remaining = members.range((start, Bound::Unbounded)).map(|(_, member)| member);
+        let page: Vec<Member> = remaining.by_ref().take(limit).cloned().collect();
+        let next_cursor = match remaining.next() {
+            Some(_) => page.last().map(|member| member.actor),
+            None => None,
+        };
+
+        MembersPage { members: page, next_cursor }
+    }
+
+    // Hand out the next invite id for a DAO
+    pub fn allocate_invite_id(&mut self, dao_id: DaoId) -> u32 {
+        let next_id = self.next_invite_id.entry(dao_id).or_insert(1);
+        let invite_id = *next_id;
+        *next_id += 1;
+        invite_id
+    }
+
+    // Whether a still redeemable invite for `invitee` uses this hash
+    pub fn has_live_invite(&self, dao_id: DaoId, invitee: &ActorId, secret_hash: &InviteHash, block: BlockNumber) -> bool {
+        self.invites.get(&dao_id).map_or(false, |invites| {
+            invites
+                .values()
+                .any(|invite| &invite.invitee == invitee && &invite.secret_hash == secret_hash && !invite.is_expired(block))
+        })
+    }
+
+    pub fn add_invite(&mut self, dao_id: DaoId, invite: Invite) {
+        self.invites.entry(dao_id).or_default().insert(invite.id, invite);
+    }
+
+    pub fn remove_invite(&mut self, dao_id: DaoId, invite_id: u32) -> Option<Invite> {
+        self.invites
+            .get_mut(&dao_id)
+            .and_then(|invites| invites.remove(&invite_id))
+    }
+
+    // Use up one redemption of `invitee`'s invite matching the hash and return its id
+    pub fn redeem_invite(&mut self, dao_id: DaoId, invitee: &ActorId, secret_hash: &InviteHash, block: BlockNumber) -> Result<u32, Error> {
+        let invites = self.invites.get_mut(&dao_id).ok_or(Error::InviteNotFound)?;
+        // A live invite wins over an expired one that reused the same secret
+        let invite = invites
+            .values_mut()
+            .filter(|invite| &invite.invitee == invitee && &invite.secret_hash == secret_hash)
+            .max_by_key(|invite| !invite.is_expired(block))
+            .ok_or(Error::InviteNotFound)?;
+        let invite_id = invite.id;
+
+        if invite.is_expired(block) {
+            invites.remove(&invite_id);
+            return Err(Error::InviteExpired);
+        }
+        invite.uses_left -= 1;
+        if invite.uses_left == 0 {
+            invites.remove(&invite_id);
+        }
+        Ok(invite_id)
+    }
+}
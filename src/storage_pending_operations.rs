@@ -0,0 +1,92 @@
+#![no_std]
+
+use gstd::ActorId;
+use gstd::collections::{BTreeMap, HashMap};
+use sails_rs::prelude::*;
+use crate::storage_dao_collection::{AdminApprovalConfig, BlockNumber, DaoId, GovernanceConfig, Role};
+use crate::Error;
+
+pub(crate) static mut PENDING_OPERATIONS: Option<PendingOperations> = None;
+
+// Admin calls that need the DAO's M-of-N admin approval
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum AdminOperation {
+    AddAdmin(ActorId),
+    RemoveAdmin(ActorId),
+    GrantRole {
+        role: Role,
+        actor: ActorId,
+    },
+    RevokeRole {
+        role: Role,
+        actor: ActorId,
+    },
+    TransferCreator(ActorId),
+    UpdateGovernance(GovernanceConfig),
+    SetAdminApproval(AdminApprovalConfig),
+    // Pay governance tokens out of the DAO treasury without a proposal
+    TreasuryTransfer {
+        to: ActorId,
+        amount: U256,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct PendingOperation {
+    pub id: u32,
+    pub operation: AdminOperation,
+    pub proposer: ActorId,
+    // Actors that confirmed the operation, the proposer included
+    pub approvals: Vec<ActorId>,
+    // Last block at which the operation can still be confirmed
+    pub expires_at: BlockNumber,
+}
+
+impl PendingOperation {
+    pub fn is_expired(&self, block: BlockNumber) -> bool {
+        block > self.expires_at
+    }
+}
+
+pub struct PendingOperations {
+    pub(crate) dao_to_operations: HashMap<DaoId, BTreeMap<u32, PendingOperation>>,
+    pub(crate) next_operation_id: HashMap<DaoId, u32>,
+}
+
+impl PendingOperations {
+    pub fn get() -> &'static Self {
+        unsafe { PENDING_OPERATIONS.as_ref().expect("PendingOperations is not initialized") }
+    }
+
+    pub fn get_mut() -> &'static mut Self {
+        unsafe { PENDING_OPERATIONS.as_mut().expect("PendingOperations is not initialized") }
+    }
+
+    // Hand out the next operation id for a DAO
+    pub fn allocate_id(&mut self, dao_id: DaoId) -> u32 {
+        let next_id = self.next_operation_id.entry(dao_id).or_insert(1);
+        let operation_id = *next_id;
+        *next_id += 1;
+        operation_id
+    }
+
+    pub fn operation_mut(&mut self, dao_id: DaoId, operation_id: u32) -> Result<&mut PendingOperation, Error> {
+        self.dao_to_operations
+            .get_mut(&dao_id)
+            .and_then(|operations| operations.get_mut(&operation_id))
+            .ok_or(Error::OperationNotFound)
+    }
+
+    // Drop the DAO's operations that can no longer be confirmed
+    pub fn prune_expired(&mut self, dao_id: DaoId, block: BlockNumber) {
+        if let Some(operations) = self.dao_to_operations.get_mut(&dao_id) {
+            operations.retain(|_, operation| !operation.is_expired(block));
+        }
+    }
+
+    pub fn remove(&mut self, dao_id: DaoId, operation_id: u32) -> Option<PendingOperation> {
+        self.dao_to_operations
+            .get_mut(&dao_id)
+            .and_then(|operations| operations.remove(&operation_id))
+    }
+}
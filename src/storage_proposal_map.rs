@@ -1,32 +1,289 @@
 #![no_std]
 
 use gstd::ActorId;
-use gstd::collections::HashMap;
+use gstd::collections::{BTreeMap, HashMap};
 use sails_rs::prelude::*;
+use crate::storage_dao_collection::{BlockNumber, DaoId, GovernanceConfig, Role, TieBreak};
+use crate::Error;
 
 pub(crate) static mut PROPOSAL_MAP: Option<ProposalMap> = None;
 
 #[derive(Clone, Debug, Encode, Decode, TypeInfo)]
 pub struct Proposal {
+    pub id: u32,
     pub title: String,
     pub description: String,
     pub creator: ActorId,
-    pub voting_start: u32,
-    pub voting_end: u32,
+    pub voting_start: BlockNumber,
+    // First block after the vote, when it can be finalized
+    pub voting_end: BlockNumber,
+    // Votes needed for quorum, fixed when the proposal is created
+    pub quorum: U256,
     pub status: ProposalStatus,
-    pub votes_for: u32,
-    pub votes_against: u32,
+    pub votes_for: U256,
+    pub votes_against: U256,
+    pub votes_abstain: U256,
+    pub votes_veto: U256,
+    pub actions: Vec<ProposalAction>,
+    pub execution_results: Vec<ActionResult>,
+    pub deposit: Option<ProposalDeposit>,
+    // A delayed message is on its way to finalize (and possibly execute) the proposal
+    pub deadline_scheduled: bool,
+}
+
+// Governance tokens the creator locked in the program when filing the proposal
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct ProposalDeposit {
+    pub amount: U256,
+    pub status: DepositStatus,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum DepositStatus {
+    // Held until the proposal is finalized
+    Locked,
+    // The creator can take it back with claim_deposit
+    Refundable,
+    Claimed,
+    // Moved into the DAO treasury
+    Slashed,
+}
+
+// Something the DAO does on its own behalf once the proposal has passed
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum ProposalAction {
+    SendMessage {
+        destination: ActorId,
+        payload: Vec<u8>,
+        value: u128,
+    },
+    // Pay governance tokens out of the DAO treasury
+    TreasuryTransfer {
+        to: ActorId,
+        amount: U256,
+    },
+    // Pay native value out of the DAO treasury
+    ValueTransfer {
+        to: ActorId,
+        amount: u128,
+        gas_limit: u64,
+    },
+    // Replace the DAO's governance settings
+    UpdateGovernance(GovernanceConfig),
+    // Remove a member from the DAO
+    KickMember(ActorId),
+    // The only way to grant a privileged role
+    GrantRole {
+        role: Role,
+        actor: ActorId,
+    },
+    RevokeRole {
+        role: Role,
+        actor: ActorId,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum ActionResult {
+    // Raw reply bytes returned by the destination
+    Success(Vec<u8>),
+    Failed(String),
+}
+
+impl Proposal {
+    // Settle a locked deposit once the outcome is known; returns the amount slashed into the treasury
+    pub fn settle_deposit(&mut self) -> Option<U256> {
+        let slash = self.status == ProposalStatus::Vetoed || self.total_votes() < self.quorum;
+        let deposit = self.deposit.as_mut().filter(|deposit| deposit.status == DepositStatus::Locked)?;
+
+        if slash {
+            deposit.status = DepositStatus::Slashed;
+            Some(deposit.amount)
+        } else {
+            deposit.status = DepositStatus::Refundable;
+            None
+        }
+    }
+
+    // Hand a locked deposit back to the creator without judging the proposal
+    pub fn release_deposit(&mut self) {
+        if let Some(deposit) = self.deposit.as_mut().filter(|deposit| deposit.status == DepositStatus::Locked) {
+            deposit.status = DepositStatus::Refundable;
+        }
+    }
+
+    pub fn is_voting_open(&self, block: BlockNumber) -> bool {
+        matches!(self.status, ProposalStatus::Pending | ProposalStatus::Active)
+            && block >= self.voting_start
+            && block < self.voting_end
+    }
+
+    // Move to `next` if the lifecycle allows it
+    pub fn transition(&mut self, next: ProposalStatus) -> Result<(), Error> {
+        if !self.status.can_transition_to(&next) {
+            return Err(Error::InvalidStatusTransition);
+        }
+        self.status = next;
+        Ok(())
+    }
+
+    // Status as of `block`, including the changes that happen by the passing of time alone
+    pub fn effective_status(&self, block: BlockNumber, config: &GovernanceConfig) -> ProposalStatus {
+        match &self.status {
+            ProposalStatus::Pending if block < self.voting_start => ProposalStatus::Pending,
+            ProposalStatus::Pending | ProposalStatus::Active if block < self.voting_end => ProposalStatus::Active,
+            ProposalStatus::Pending | ProposalStatus::Active => self.outcome(config),
+            ProposalStatus::Queued { eta_block }
+                if config.execution_grace_period != 0
+                    && block > eta_block.saturating_add(config.execution_grace_period) => ProposalStatus::Expired,
+            status => status.clone(),
+        }
+    }
+
+    // Persist the time-driven transitions up to `block`
+    pub fn refresh_status(&mut self, block: BlockNumber, config: &GovernanceConfig) -> Result<(), Error> {
+        let target = self.effective_status(block, config);
+        if self.status == ProposalStatus::Pending && target != ProposalStatus::Pending {
+            self.transition(ProposalStatus::Active)?;
+        }
+        if self.status != target {
+            self.transition(target)?;
+        }
+        Ok(())
+    }
+
+    fn tally_mut(&mut self, option: VoteOption) -> &mut U256 {
+        match option {
+            VoteOption::For => &mut self.votes_for,
+            VoteOption::Against => &mut self.votes_against,
+            VoteOption::Abstain => &mut self.votes_abstain,
+            VoteOption::NoWithVeto => &mut self.votes_veto,
+        }
+    }
+
+    // Add a ballot's weight to the matching tally
+    pub fn add_ballot(&mut self, ballot: &Ballot) {
+        *self.tally_mut(ballot.option) += ballot.weight;
+    }
+
+    // Take a previously counted ballot back out of the tallies
+    pub fn remove_ballot(&mut self, ballot: &Ballot) {
+        *self.tally_mut(ballot.option) -= ballot.weight;
+    }
+
+    pub fn total_votes(&self) -> U256 {
+        self.votes_for + self.votes_against + self.votes_abstain + self.votes_veto
+    }
+
+    // Tallies are compared as 512-bit products, which cannot overflow
+    pub fn is_vetoed(&self, veto_threshold_percent: u8) -> bool {
+        let total = self.total_votes();
+        !total.is_zero() && self.votes_veto.full_mul(U256::from(100)) > total.full_mul(U256::from(veto_threshold_percent))
+    }
+
+    // Abstentions count toward quorum but not toward the outcome; NoWithVeto counts as a vote against
+    pub fn is_approved(&self, config: &GovernanceConfig) -> bool {
+        let decisive = self.votes_for + self.votes_against + self.votes_veto;
+        if decisive.is_zero() {
+            return false;
+        }
+
+        let (numerator, denominator) = config.approval_threshold.ratio();
+        let support = self.votes_for.full_mul(U256::from(denominator));
+        let required = decisive.full_mul(U256::from(numerator));
+
+        if support == required {
+            config.tie_break == TieBreak::Pass
+        } else {
+            support > required
+        }
+    }
+
+    // Status the tallies lead to once voting is over
+    pub fn outcome(&self, config: &GovernanceConfig) -> ProposalStatus {
+        if self.total_votes() < self.quorum {
+            ProposalStatus::QuorumNotReached
+        } else if self.is_vetoed(config.veto_threshold_percent) {
+            ProposalStatus::Vetoed
+        } else if self.is_approved(config) {
+            ProposalStatus::Succeeded
+        } else {
+            ProposalStatus::Defeated
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, TypeInfo)]
 pub enum ProposalStatus {
+    // Not yet submitted for voting
+    Draft,
+    // Submitted, voting has not started
+    Pending,
     Active,
-    Passed,
-    Rejected,
+    Succeeded,
+    // Rejected by the voters
+    Defeated,
+    // Too few tokens took part in the vote
+    QuorumNotReached,
+    Queued { eta_block: BlockNumber },
+    Executed,
+    // One of the actions failed while executing
+    ExecutionFailed,
+    Cancelled,
+    // Stayed queued past the DAO's execution grace period
+    Expired,
+    Vetoed,
+}
+
+impl ProposalStatus {
+    // The single table of allowed lifecycle transitions
+    pub fn can_transition_to(&self, next: &ProposalStatus) -> bool {
+        use ProposalStatus::*;
+
+        matches!(
+            (self, next),
+            (Draft, Pending)
+                | (Draft, Cancelled)
+                | (Pending, Active)
+                | (Pending, Cancelled)
+                | (Active, Succeeded)
+                | (Active, Defeated)
+                | (Active, QuorumNotReached)
+                | (Active, Vetoed)
+                | (Active, Cancelled)
+                | (Succeeded, Queued { .. })
+                | (Succeeded, Cancelled)
+                | (Queued { .. }, Executed)
+                | (Queued { .. }, Cancelled)
+                | (Queued { .. }, Expired)
+                // Execution is marked up front, an action can still fail afterwards
+                | (Executed, ExecutionFailed)
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, TypeInfo)]
+pub enum VoteOption {
+    For,
+    Against,
+    Abstain,
+    NoWithVeto,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct Ballot {
+    pub option: VoteOption,
+    pub weight: U256,
 }
 
 pub struct ProposalMap {
-    pub(crate) dao_to_proposals: HashMap<String, Vec<Proposal>>,
+    // Proposals of each DAO by id; ids start at 1 and are never reused
+    pub(crate) dao_to_proposals: HashMap<DaoId, BTreeMap<u32, Proposal>>,
+    pub(crate) next_proposal_id: HashMap<DaoId, u32>,
+    // Ballots cast on each proposal, keyed by (dao id, proposal id) and then by voter
+    pub(crate) ballots: HashMap<(DaoId, u32), HashMap<ActorId, Ballot>>,
+    // Token balances taken when each proposal was created; a ballot weighs what the voter held then
+    pub(crate) snapshots: HashMap<(DaoId, u32), HashMap<ActorId, U256>>,
 }
 
 impl ProposalMap {
@@ -38,4 +295,33 @@ impl ProposalMap {
         unsafe { PROPOSAL_MAP.as_mut().expect("ProposalMap is not initialized") }
     }
 
+    // Hand out the next proposal id for a DAO
+    pub fn allocate_id(&mut self, dao_id: DaoId) -> u32 {
+        let next_id = self.next_proposal_id.entry(dao_id).or_insert(1);
+        let proposal_id = *next_id;
+        *next_id += 1;
+        proposal_id
+    }
+
+    pub fn voting_power(&self, dao_id: DaoId, proposal_id: u32, voter: &ActorId) -> U256 {
+        self.snapshots
+            .get(&(dao_id, proposal_id))
+            .and_then(|snapshot| snapshot.get(voter))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn proposal(&self, dao_id: DaoId, proposal_id: u32) -> Result<&Proposal, Error> {
+        self.dao_to_proposals
+            .get(&dao_id)
+            .and_then(|proposals| proposals.get(&proposal_id))
+            .ok_or(Error::ProposalNotFound)
+    }
+
+    pub fn proposal_mut(&mut self, dao_id: DaoId, proposal_id: u32) -> Result<&mut Proposal, Error> {
+        self.dao_to_proposals
+            .get_mut(&dao_id)
+            .and_then(|proposals| proposals.get_mut(&proposal_id))
+            .ok_or(Error::ProposalNotFound)
+    }
 }
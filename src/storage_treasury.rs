@@ -0,0 +1,62 @@
+#![no_std]
+
+use gstd::collections::HashMap;
+use sails_rs::prelude::*;
+use crate::storage_dao_collection::DaoId;
+
+pub(crate) static mut TREASURY: Option<Treasury> = None;
+
+pub struct Treasury {
+    // Governance token held by the program on behalf of each DAO
+    pub(crate) vft_balances: HashMap<DaoId, U256>,
+    // Share of the program's native balance that belongs to each DAO
+    pub(crate) value_balances: HashMap<DaoId, u128>,
+}
+
+impl Treasury {
+    pub fn get() -> &'static Self {
+        unsafe { TREASURY.as_ref().expect("Treasury is not initialized") }
+    }
+
+    pub fn get_mut() -> &'static mut Self {
+        unsafe { TREASURY.as_mut().expect("Treasury is not initialized") }
+    }
+
+    pub fn vft_balance(&self, dao_id: DaoId) -> U256 {
+        self.vft_balances.get(&dao_id).copied().unwrap_or_default()
+    }
+
+    pub fn credit_vft(&mut self, dao_id: DaoId, amount: U256) {
+        *self.vft_balances.entry(dao_id).or_default() += amount;
+    }
+
+    // Returns false and leaves the balance untouched when the DAO holds less than `amount`
+    pub fn debit_vft(&mut self, dao_id: DaoId, amount: U256) -> bool {
+        match self.vft_balances.get_mut(&dao_id) {
+            Some(balance) if *balance >= amount => {
+                *balance -= amount;
+                true
+            }
+            _ => amount.is_zero(),
+        }
+    }
+
+    pub fn value_balance(&self, dao_id: DaoId) -> u128 {
+        self.value_balances.get(&dao_id).copied().unwrap_or_default()
+    }
+
+    pub fn credit_value(&mut self, dao_id: DaoId, amount: u128) {
+        *self.value_balances.entry(dao_id).or_default() += amount;
+    }
+
+    // Returns false and leaves the balance untouched when the DAO holds less than `amount`
+    pub fn debit_value(&mut self, dao_id: DaoId, amount: u128) -> bool {
+        match self.value_balances.get_mut(&dao_id) {
+            Some(balance) if *balance >= amount => {
+                *balance -= amount;
+                true
+            }
+            _ => amount == 0,
+        }
+    }
+}
@@ -0,0 +1,53 @@
+#![no_std]
+
+use gstd::ActorId;
+use sails_rs::calls::ActionIo;
+use sails_rs::prelude::*;
+use nexus_vft_client::nexus_vft::io as vft_io;
+use crate::Error;
+
+// Send an encoded call to the VFT program `token` and decode its reply
+async fn call<A: ActionIo>(token: ActorId, call_payload: Vec<u8>) -> Result<A::Reply, Error> {
+    let reply_bytes = gstd::msg::send_bytes_for_reply(token, call_payload, 0, 0)
+        .map_err(|_| Error::TokenCallFailed)?
+        .await
+        .map_err(|_| Error::TokenCallFailed)?;
+    A::decode_reply(&reply_bytes).map_err(|_| Error::TokenCallFailed)
+}
+
+pub(crate) async fn name(token: ActorId) -> Result<String, Error> {
+    call::<vft_io::Name>(token, vft_io::Name::encode_call()).await
+}
+
+pub(crate) async fn symbol(token: ActorId) -> Result<String, Error> {
+    call::<vft_io::Symbol>(token, vft_io::Symbol::encode_call()).await
+}
+
+pub(crate) async fn decimals(token: ActorId) -> Result<u8, Error> {
+    call::<vft_io::Decimals>(token, vft_io::Decimals::encode_call()).await
+}
+
+// Query the token balance of `account` on the VFT program `token`
+pub(crate) async fn balance_of(token: ActorId, account: ActorId) -> Result<U256, Error> {
+    call::<vft_io::BalanceOf>(token, vft_io::BalanceOf::encode_call(account)).await
+}
+
+// Query every holder's balance on the VFT program `token`
+pub(crate) async fn balances(token: ActorId) -> Result<Vec<(ActorId, U256)>, Error> {
+    call::<vft_io::Balances>(token, vft_io::Balances::encode_call()).await
+}
+
+// Query the total supply of the VFT program `token`
+pub(crate) async fn total_supply(token: ActorId) -> Result<U256, Error> {
+    call::<vft_io::TotalSupply>(token, vft_io::TotalSupply::encode_call()).await
+}
+
+// Transfer `value` of the program's own tokens to `to`
+pub(crate) async fn transfer(token: ActorId, to: ActorId, value: U256) -> Result<bool, Error> {
+    call::<vft_io::Transfer>(token, vft_io::Transfer::encode_call(to, value)).await
+}
+
+// Move `value` tokens from `from` to `to` using the allowance granted to the program
+pub(crate) async fn transfer_from(token: ActorId, from: ActorId, to: ActorId, value: U256) -> Result<bool, Error> {
+    call::<vft_io::TransferFrom>(token, vft_io::TransferFrom::encode_call(from, to, value)).await
+}
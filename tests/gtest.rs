@@ -1,13 +1,15 @@
+use blake2::{Blake2b, Digest, digest::consts::U32};
 use gstd::ActorId;
 use gstd::str::FromStr;
 use sails_rs::{calls::*, CodeId, gtest::calls::*, U256};
-use nexus_dao_client::ProposalStatus;
+use nexus_dao_client::{ActionResult, AdminApprovalConfig, AdminOperation, ApprovalThreshold, DaoRef, DaoRelationship, DepositStatus, Error, FinalizationMode, GovernanceConfig, MembershipPolicy, ProposalAction, ProposalStatus, ProposerPolicy, Quorum, Role, TieBreak, VoteOption};
 use vft_service::Service as VftService;
 
 use nexus_dao_client::traits::*;
 
 const ACTOR_ID: u64 = 42;
 const NEW_ADMIN: u64 = 43;
+const THIRD_ADMIN: u64 = 44;
 
 #[tokio::test]
 async fn create_and_query_multiple_daos() {
@@ -32,21 +34,23 @@ async fn create_and_query_multiple_daos() {
     let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "TOKEN1".to_string(), "T1".to_string(), [].to_vec()).await;
     // Create multiple DAOs
     let _ = service_client
-        .create_dao("DAO1".into(), "First DAO".into(), nexus_vft_id)
+        .create_dao("DAO1".into(), "First DAO".into(), nexus_vft_id, default_governance())
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
     let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "TOKEN2".to_string(), "T2".to_string(), [].to_vec()).await;
     let _ = service_client
-        .create_dao("DAO2".into(), "Second DAO".into(), nexus_vft_id)
+        .create_dao("DAO2".into(), "Second DAO".into(), nexus_vft_id, default_governance())
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
     // Verify the first DAO's info
     let dao_info_1 = service_client
-        .get_dao_info("DAO1".to_string())
+        .get_dao_info(dao("DAO1"))
         .recv(program_id)
         .await
         .unwrap().unwrap();
@@ -59,7 +63,7 @@ async fn create_and_query_multiple_daos() {
 
     // Verify the second DAO's info
     let dao_info_2 = service_client
-        .get_dao_info("DAO2".to_string())
+        .get_dao_info(dao("DAO2"))
         .recv(program_id)
         .await
         .unwrap().unwrap();
@@ -73,10 +77,10 @@ async fn create_and_query_multiple_daos() {
         .get_daos_by_actor(ACTOR_ID.into())
         .recv(program_id)
         .await
-        .unwrap()
         .unwrap();
+    let names: Vec<String> = daos.into_iter().map(|dao| dao.name).collect();
 
-    assert_eq!(daos, vec!["DAO1".to_string(), "DAO2".to_string()]);
+    assert_eq!(names, vec!["DAO1".to_string(), "DAO2".to_string()]);
 }
 
 #[tokio::test]
@@ -101,43 +105,46 @@ async fn add_admin_and_check_admin() {
     let nexus_vft_id = get_vft_id(remoting, ACTOR_ID.into(), "Token".to_string(), "Symbol".to_string(), [].to_vec()).await;
     // Create a DAO
     let _ = service_client
-        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id)
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
     // Add a new admin
     let _ = service_client
-        .add_admin("TestDAO".into(), NEW_ADMIN.into())
+        .add_admin(dao("TestDAO"), NEW_ADMIN.into())
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
     // Verify the new admin
     let is_admin = service_client
-        .is_admin("TestDAO".into(), NEW_ADMIN.into())
+        .is_admin(dao("TestDAO"), NEW_ADMIN.into())
         .recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
     assert!(is_admin);
 
     // Check if the original creator is still an admin
     let is_creator_admin = service_client
-        .is_admin("TestDAO".into(), ACTOR_ID.into())
+        .is_admin(dao("TestDAO"), ACTOR_ID.into())
         .recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
     assert!(is_creator_admin);
 }
 
 #[tokio::test]
-async fn test_dao_creation() {
+async fn remove_admin_and_transfer_creator() {
     let remoting = GTestRemoting::new(ACTOR_ID.into());
     remoting.system().init_logger();
 
-    // Submit program code into the system
     let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
 
     let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
@@ -151,75 +158,85 @@ async fn test_dao_creation() {
 
     let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
     service_client
-        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id)
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
-    let dao_info = service_client
-        .get_dao_info("TestDAO".to_string())
-        .recv(program_id)
+    service_client
+        .add_admin(dao("TestDAO"), NEW_ADMIN.into())
+        .send_recv(program_id)
         .await
         .unwrap()
         .unwrap();
 
-    assert_eq!(dao_info.name, "TestDAO".to_string());
-    assert_eq!(dao_info.description, "A test DAO".to_string());
-    assert_eq!(dao_info.token.name, "DAO Token".to_string());
-    assert_eq!(dao_info.token.symbol, "DT".to_string());
-}
-
-#[tokio::test]
-async fn test_proposal_creation() {
-    let remoting = GTestRemoting::new(ACTOR_ID.into());
-    remoting.system().init_logger();
+    // Another admin cannot push the creator out
+    let result = service_client
+        .remove_admin(dao("TestDAO"), ACTOR_ID.into())
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
 
-    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+    assert_eq!(result, Err(Error::CannotRemoveCreator));
 
-    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
-    let program_id = program_factory
-        .new()
-        .send_recv(program_code_id, b"salt")
+    let result = service_client
+        .transfer_creator(dao("TestDAO"), NEW_ADMIN.into())
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
         .await
         .unwrap();
 
-    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+    assert_eq!(result, Err(Error::Unauthorized));
 
-    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
     service_client
-        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id)
+        .transfer_creator(dao("TestDAO"), NEW_ADMIN.into())
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
     service_client
-        .create_proposal("TestDAO".into(), "Proposal 1".into(), "Detail 1".into(), 10, 20)
+        .remove_admin(dao("TestDAO"), ACTOR_ID.into())
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
-    let proposals = service_client
-        .get_proposals("TestDAO".to_string())
+    let is_admin = service_client
+        .is_admin(dao("TestDAO"), ACTOR_ID.into())
         .recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
-    assert_eq!(proposals.len(), 1);
-    assert_eq!(proposals[0].description, "Detail 1");
+    assert!(!is_admin);
 
-    // Call get_proposal to verify the proposal by id
-    let proposal = service_client
-        .get_proposal("TestDAO".to_string(), 1)
+    let daos = service_client
+        .get_daos_by_actor(NEW_ADMIN.into())
         .recv(program_id)
         .await
         .unwrap();
 
-    assert_eq!(proposal.unwrap().description, "Detail 1");
+    assert_eq!(daos.len(), 1);
+    assert_eq!(daos[0].name, "TestDAO".to_string());
+    assert_eq!(daos[0].relationships, vec![DaoRelationship::Creator]);
 
+    // The last remaining admin is the creator and cannot be removed
+    let result = service_client
+        .remove_admin(dao("TestDAO"), NEW_ADMIN.into())
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::CannotRemoveCreator));
 }
 
 #[tokio::test]
-async fn test_proposal_voting() {
+async fn grant_and_revoke_roles() {
     let remoting = GTestRemoting::new(ACTOR_ID.into());
     remoting.system().init_logger();
 
@@ -234,61 +251,127 @@ async fn test_proposal_voting() {
 
     let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
 
-    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
     service_client
-        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id)
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Privileged roles are not an admin's to hand out
+    let result = service_client
+        .grant_role(dao("TestDAO"), Role::Executor, NEW_ADMIN.into())
         .send_recv(program_id)
         .await
         .unwrap();
 
+    assert_eq!(result, Err(Error::RoleRequiresProposal));
+
+    let action = ProposalAction::GrantRole { role: Role::Executor, actor: NEW_ADMIN.into() };
     let proposal_id = service_client
-        .create_proposal("TestDAO".into(), "Proposal 1".into(), "Detail 1".into(), 10, 20)
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![action])
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
-    let vote_result = service_client
-        .vote_on_proposal("TestDAO".into(), proposal_id, true)
+    remoting.system().spend_blocks(15);
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
         .send_recv(program_id)
-        .await.unwrap();;
+        .await
+        .unwrap()
+        .unwrap();
 
-    println!("res A  = {:?}", vote_result);
-    assert!(vote_result.is_err());
+    remoting.system().spend_blocks(21);
+    service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
 
-    remoting.system().spend_blocks(11);
-    let vote_result = service_client
-        .vote_on_proposal("TestDAO".into(), proposal_id, true)
+    // Only holders of the Executor role may execute
+    let result = service_client
+        .execute_proposal(dao("TestDAO"), proposal_id)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
         .send_recv(program_id)
-        .await.unwrap();;
+        .await
+        .unwrap();
 
-    assert!(vote_result.is_ok());
+    assert_eq!(result, Err(Error::Unauthorized));
 
-    let proposals = service_client
-        .get_proposals("TestDAO".to_string())
+    service_client
+        .execute_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let has_role = service_client
+        .has_role(dao("TestDAO"), Role::Executor, NEW_ADMIN.into())
         .recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
-    assert_eq!(proposals[0].votes_for, 1);
+    assert!(has_role);
 
-    let vote_result = service_client
-        .vote_on_proposal("TestDAO".into(), proposal_id, false)
+    // Other roles are still granted by admins directly
+    service_client
+        .grant_role(dao("TestDAO"), Role::MemberManager, NEW_ADMIN.into())
         .send_recv(program_id)
-        .await.unwrap();;
+        .await
+        .unwrap()
+        .unwrap();
 
-    assert!(vote_result.is_ok());
+    let result = service_client
+        .grant_role(dao("TestDAO"), Role::MemberManager, NEW_ADMIN.into())
+        .send_recv(program_id)
+        .await
+        .unwrap();
 
-    let proposals = service_client
-        .get_proposals("TestDAO".to_string())
+    assert_eq!(result, Err(Error::RoleAlreadyGranted));
+
+    // An executor is not an admin and cannot hand out roles
+    let result = service_client
+        .grant_role(dao("TestDAO"), Role::Guardian, NEW_ADMIN.into())
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::Unauthorized));
+
+    // Admins can take a privileged role away without a vote
+    service_client
+        .revoke_role(dao("TestDAO"), Role::Executor, NEW_ADMIN.into())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let has_role = service_client
+        .has_role(dao("TestDAO"), Role::Executor, NEW_ADMIN.into())
         .recv(program_id)
         .await
+        .unwrap()
+        .unwrap();
+
+    assert!(!has_role);
+
+    let result = service_client
+        .revoke_role(dao("TestDAO"), Role::Admin, ACTOR_ID.into())
+        .send_recv(program_id)
+        .await
         .unwrap();
 
-    assert_eq!(proposals[0].votes_against, 1);
+    assert_eq!(result, Err(Error::CannotRemoveCreator));
 }
 
 #[tokio::test]
-async fn test_proposal_finalization() {
+async fn multisig_admin_operations() {
     let remoting = GTestRemoting::new(ACTOR_ID.into());
     remoting.system().init_logger();
 
@@ -305,75 +388,172 @@ async fn test_proposal_finalization() {
 
     let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
     service_client
-        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id)
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
-    let proposal_id = service_client
-        .create_proposal("TestDAO".into(), "Proposal 1".into(), "Detail 1".into(), 10, 20)
+    // With the default threshold of one these take effect immediately
+    service_client
+        .add_admin(dao("TestDAO"), NEW_ADMIN.into())
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
-    remoting.system().spend_blocks(15);
+    let result = service_client
+        .set_admin_approval(dao("TestDAO"), AdminApprovalConfig { threshold: 3, ttl: 10 })
+        .send_recv(program_id)
+        .await
+        .unwrap();
 
-    let _ = service_client
-        .vote_on_proposal("TestDAO".into(), proposal_id, true)
+    assert_eq!(result, Err(Error::InvalidApprovalConfig));
+
+    service_client
+        .set_admin_approval(dao("TestDAO"), AdminApprovalConfig { threshold: 2, ttl: 10 })
         .send_recv(program_id)
-        .await.unwrap();;
+        .await
+        .unwrap()
+        .unwrap();
 
-    // Simulate passing the voting period
-    remoting.system().spend_blocks(21);
+    // Now a single admin can only propose
+    service_client
+        .add_admin(dao("TestDAO"), THIRD_ADMIN.into())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
 
-    let _ = service_client
-        .finalize_proposal("TestDAO".into(), proposal_id)
+    let is_admin = service_client
+        .is_admin(dao("TestDAO"), THIRD_ADMIN.into())
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(!is_admin);
+
+    let pending = service_client
+        .get_pending_operations(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].operation, AdminOperation::AddAdmin(THIRD_ADMIN.into()));
+    let operation_id = pending[0].id;
+
+    let result = service_client
+        .confirm_admin_operation(dao("TestDAO"), operation_id)
         .send_recv(program_id)
         .await
         .unwrap();
 
-    let proposals = service_client
-        .get_proposals("TestDAO".to_string())
+    assert_eq!(result, Err(Error::AlreadyApproved));
+
+    service_client
+        .confirm_admin_operation(dao("TestDAO"), operation_id)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let is_admin = service_client
+        .is_admin(dao("TestDAO"), THIRD_ADMIN.into())
         .recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
-    assert_eq!(proposals[0].status, ProposalStatus::Passed);
-}
+    assert!(is_admin);
 
-#[tokio::test]
-async fn test_vote_on_nonexistent_proposal() {
-    let remoting = GTestRemoting::new(ACTOR_ID.into());
-    remoting.system().init_logger();
+    // Operations that are not confirmed in time lapse
+    service_client
+        .remove_admin(dao("TestDAO"), THIRD_ADMIN.into())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
 
-    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+    let pending = service_client
+        .get_pending_operations(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let operation_id = pending[0].id;
 
-    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
-    let program_id = program_factory
-        .new()
-        .send_recv(program_code_id, b"salt")
+    remoting.system().spend_blocks(11);
+
+    let result = service_client
+        .confirm_admin_operation(dao("TestDAO"), operation_id)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
         .await
         .unwrap();
 
-    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+    assert_eq!(result, Err(Error::OperationExpired));
+
+    let pending = service_client
+        .get_pending_operations(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(pending.is_empty());
+
+    // An operation that no longer applies stays open instead of being dropped
+    for _ in 0..2 {
+        service_client
+            .remove_admin(dao("TestDAO"), THIRD_ADMIN.into())
+            .send_recv(program_id)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    let pending = service_client
+        .get_pending_operations(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let (first_id, second_id) = (pending[0].id, pending[1].id);
 
-    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
     service_client
-        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id)
+        .confirm_admin_operation(dao("TestDAO"), first_id)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
     let result = service_client
-        .vote_on_proposal("TestDAO".into(), 999, true)
+        .confirm_admin_operation(dao("TestDAO"), second_id)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
         .send_recv(program_id)
-        .await;
+        .await
+        .unwrap();
 
-    assert!(result.is_err());
+    assert_eq!(result, Err(Error::NotAdmin));
+
+    let pending = service_client
+        .get_pending_operations(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, second_id);
 }
 
 #[tokio::test]
-async fn test_finalize_proposal_before_voting_end() {
+async fn multisig_governance_and_treasury() {
     let remoting = GTestRemoting::new(ACTOR_ID.into());
     remoting.system().init_logger();
 
@@ -388,31 +568,2855 @@ async fn test_finalize_proposal_before_voting_end() {
 
     let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
 
-    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
     service_client
-        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id)
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
-    let proposal_id = service_client
-        .create_proposal("TestDAO".into(), "Proposal 1".into(), "Detail 1".into(), 10, 20)
+    let mut vft_client = nexus_vft_client::NexusVft::new(remoting.clone());
+    vft_client
+        .approve(program_id, U256::from(40))
+        .send_recv(nexus_vft_id)
+        .await
+        .unwrap();
+    service_client
+        .deposit_to_treasury(dao("TestDAO"), U256::from(40))
         .send_recv(program_id)
         .await
+        .unwrap()
         .unwrap();
 
-    remoting.system().spend_blocks(5);
+    service_client
+        .add_admin(dao("TestDAO"), NEW_ADMIN.into())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    service_client
+        .set_admin_approval(dao("TestDAO"), AdminApprovalConfig { threshold: 2, ttl: 10 })
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
 
     let result = service_client
-        .finalize_proposal("TestDAO".into(), proposal_id)
+        .treasury_transfer(dao("TestDAO"), THIRD_ADMIN.into(), U256::from(30))
+        .with_args(GTestArgs::new(THIRD_ADMIN.into()))
         .send_recv(program_id)
         .await
         .unwrap();
 
-    assert!(result.is_err());
-}
+    assert_eq!(result, Err(Error::Unauthorized));
 
-use nexus_vft_client::traits::*;
+    let result = service_client
+        .treasury_transfer(dao("TestDAO"), THIRD_ADMIN.into(), U256::from(50))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InsufficientTreasury));
+
+    // One admin alone can only propose a treasury transfer
+    service_client
+        .treasury_transfer(dao("TestDAO"), THIRD_ADMIN.into(), U256::from(30))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let balance = vft_client
+        .balance_of(THIRD_ADMIN.into())
+        .recv(nexus_vft_id)
+        .await
+        .unwrap();
+
+    assert_eq!(balance, U256::zero());
+
+    let pending = service_client
+        .get_pending_operations(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(pending[0].operation, AdminOperation::TreasuryTransfer { to: THIRD_ADMIN.into(), amount: U256::from(30) });
+
+    service_client
+        .confirm_admin_operation(dao("TestDAO"), pending[0].id)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let balance = vft_client
+        .balance_of(THIRD_ADMIN.into())
+        .recv(nexus_vft_id)
+        .await
+        .unwrap();
+
+    assert_eq!(balance, U256::from(30));
+
+    let treasury = service_client
+        .get_treasury(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(treasury, U256::from(10));
+
+    // Governance changes need the same approvals
+    let governance = GovernanceConfig {
+        execution_delay: 5,
+        ..default_governance()
+    };
+    service_client
+        .update_governance(dao("TestDAO"), governance.clone())
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let config = service_client
+        .get_governance_config(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(config, default_governance());
+
+    let pending = service_client
+        .get_pending_operations(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    service_client
+        .confirm_admin_operation(dao("TestDAO"), pending[0].id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let config = service_client
+        .get_governance_config(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(config, governance);
+}
+
+#[tokio::test]
+async fn join_leave_and_list_members() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
+    service_client
+        .create_dao("OpenDAO".into(), "Anyone can join".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let governance = GovernanceConfig {
+        membership_policy: MembershipPolicy::ApprovalRequired,
+        ..default_governance()
+    };
+    service_client
+        .create_dao("ClosedDAO".into(), "Members are vetted".into(), nexus_vft_id, governance)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    service_client
+        .join_dao(dao("OpenDAO"))
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .join_dao(dao("OpenDAO"))
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::AlreadyMember));
+
+    // Joining the closed DAO only files an application
+    service_client
+        .join_dao(dao("ClosedDAO"))
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let is_member = service_client
+        .is_member(dao("ClosedDAO"), NEW_ADMIN.into())
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(!is_member);
+
+    let result = service_client
+        .approve_member(dao("ClosedDAO"), THIRD_ADMIN.into())
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::NoPendingApplication));
+
+    service_client
+        .approve_member(dao("ClosedDAO"), NEW_ADMIN.into())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let daos = service_client
+        .get_daos_by_actor(NEW_ADMIN.into())
+        .recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(daos.len(), 2);
+    assert!(daos.iter().all(|dao| dao.relationships == vec![DaoRelationship::Member]));
+
+    let daos = service_client
+        .get_daos_by_actor(ACTOR_ID.into())
+        .recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(daos[0].relationships, vec![DaoRelationship::Creator, DaoRelationship::Member]);
+
+    // Page through the creator and the new member one at a time
+    let page = service_client
+        .get_members(dao("OpenDAO"), None, 1)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(page.members.len(), 1);
+    assert!(page.next_cursor.is_some());
+
+    let page = service_client
+        .get_members(dao("OpenDAO"), page.next_cursor, 1)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(page.members.len(), 1);
+    assert_eq!(page.next_cursor, None);
+
+    service_client
+        .leave_dao(dao("OpenDAO"))
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let page = service_client
+        .get_members(dao("OpenDAO"), None, 10)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(page.members.len(), 1);
+    assert_eq!(page.members[0].actor, ACTOR_ID.into());
+}
+
+#[tokio::test]
+async fn kick_member_by_proposal() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    service_client
+        .join_dao(dao("TestDAO"))
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Kick".into(), "Remove a member".into(), 10, 20, vec![ProposalAction::KickMember(NEW_ADMIN.into())])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(15);
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(21);
+    service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    service_client
+        .execute_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::Executed);
+
+    let is_member = service_client
+        .is_member(dao("TestDAO"), NEW_ADMIN.into())
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(!is_member);
+
+    let page = service_client
+        .get_members(dao("TestDAO"), None, 10)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(page.members.len(), 1);
+    assert_eq!(page.members[0].actor, ACTOR_ID.into());
+
+    let daos = service_client
+        .get_daos_by_actor(NEW_ADMIN.into())
+        .recv(program_id)
+        .await
+        .unwrap();
+
+    assert!(daos.is_empty());
+}
+
+#[tokio::test]
+async fn invite_codes() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
+    let governance = GovernanceConfig {
+        membership_policy: MembershipPolicy::InviteOnly,
+        ..default_governance()
+    };
+    service_client
+        .create_dao("PrivateDAO".into(), "By invitation only".into(), nexus_vft_id, governance)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .join_dao(dao("PrivateDAO"))
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InviteOnly));
+
+    // Only the hash of the secret goes on chain when the invite is created
+    let secret = b"welcome-aboard".to_vec();
+    let secret_hash: [u8; 32] = Blake2b::<U32>::digest(&secret).into();
+    let expires_at = remoting.system().block_height() + 10;
+
+    let result = service_client
+        .create_invite(dao("PrivateDAO"), NEW_ADMIN.into(), secret_hash, expires_at, 1)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::Unauthorized));
+
+    let invite_id = service_client
+        .create_invite(dao("PrivateDAO"), NEW_ADMIN.into(), secret_hash, expires_at, 1)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .create_invite(dao("PrivateDAO"), NEW_ADMIN.into(), secret_hash, expires_at, 1)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InviteAlreadyExists));
+
+    let result = service_client
+        .join_with_invite(dao("PrivateDAO"), b"wrong-secret".to_vec())
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InviteNotFound));
+
+    // The secret is of no use to anyone but the invitee
+    let result = service_client
+        .join_with_invite(dao("PrivateDAO"), secret.clone())
+        .with_args(GTestArgs::new(THIRD_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InviteNotFound));
+
+    service_client
+        .join_with_invite(dao("PrivateDAO"), secret.clone())
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let is_member = service_client
+        .is_member(dao("PrivateDAO"), NEW_ADMIN.into())
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(is_member);
+
+    // The single use is spent
+    service_client
+        .leave_dao(dao("PrivateDAO"))
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .join_with_invite(dao("PrivateDAO"), secret.clone())
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InviteNotFound));
+
+    // A revoked invite can no longer be redeemed
+    let invite_id_2 = service_client
+        .create_invite(dao("PrivateDAO"), THIRD_ADMIN.into(), secret_hash, expires_at, 5)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_ne!(invite_id, invite_id_2);
+
+    let invites = service_client
+        .get_invites(dao("PrivateDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(invites.len(), 1);
+    assert_eq!(invites[0].invitee, ActorId::from(THIRD_ADMIN));
+    assert_eq!(invites[0].uses_left, 5);
+
+    service_client
+        .revoke_invite(dao("PrivateDAO"), invite_id_2)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .join_with_invite(dao("PrivateDAO"), secret.clone())
+        .with_args(GTestArgs::new(THIRD_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InviteNotFound));
+
+    // Invites stop working after their expiry block
+    service_client
+        .create_invite(dao("PrivateDAO"), THIRD_ADMIN.into(), secret_hash, expires_at, 5)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(11);
+
+    let result = service_client
+        .join_with_invite(dao("PrivateDAO"), secret)
+        .with_args(GTestArgs::new(THIRD_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InviteExpired));
+}
+
+#[tokio::test]
+async fn test_dao_creation() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    // Submit program code into the system
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
+    let dao_id = service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let dao_info = service_client
+        .get_dao_info(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(dao_info.id, dao_id);
+    assert_eq!(dao_info.name, "TestDAO".to_string());
+    assert_eq!(dao_info.description, "A test DAO".to_string());
+    assert_eq!(dao_info.token.name, "DAO Token".to_string());
+    assert_eq!(dao_info.token.symbol, "DT".to_string());
+
+    // The same DAO is reachable by id and by a differently cased name
+    for dao_ref in [DaoRef::Id(dao_id), dao("testdao")] {
+        let dao_info = service_client
+            .get_dao_info(dao_ref)
+            .recv(program_id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(dao_info.name, "TestDAO".to_string());
+    }
+}
+
+#[tokio::test]
+async fn test_dao_name_uniqueness() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
+    service_client
+        .create_dao("Test_DAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Another account cannot take over the name or a lookalike of it
+    for name in ["Test_DAO", "test-dao", "TEST_dao"] {
+        let result = service_client
+            .create_dao(name.into(), "Impostor".into(), nexus_vft_id, default_governance())
+            .with_args(GTestArgs::new(NEW_ADMIN.into()))
+            .send_recv(program_id)
+            .await
+            .unwrap();
+
+        assert_eq!(result, Err(Error::DaoAlreadyExists));
+    }
+
+    let result = service_client
+        .create_dao("bad name!".into(), "".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InvalidDaoName));
+
+    let result = service_client
+        .create_dao("Nexus_DAO".into(), "".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::ReservedDaoName));
+
+    let dao_info = service_client
+        .get_dao_info(dao("Test_DAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(dao_info.description, "A test DAO".to_string());
+
+    let daos = service_client
+        .get_daos_by_actor(NEW_ADMIN.into())
+        .recv(program_id)
+        .await
+        .unwrap();
+
+    assert!(daos.is_empty());
+}
+
+#[tokio::test]
+async fn test_proposal_creation() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposals = service_client
+        .get_proposals(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0].id, 1);
+    assert_eq!(proposals[0].description, "Detail 1");
+
+    // Call get_proposal to verify the proposal by id
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), 1)
+        .recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(proposal.unwrap().description, "Detail 1");
+
+    // Ids start at 1, so 0 is simply unknown rather than a trap
+    for proposal_id in [0, 2, u32::MAX] {
+        let proposal = service_client
+            .get_proposal(dao("TestDAO"), proposal_id)
+            .recv(program_id)
+            .await
+            .unwrap();
+
+        assert_eq!(proposal, Err(Error::ProposalNotFound));
+    }
+}
+
+#[tokio::test]
+async fn test_proposer_policy() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
+    let governance = GovernanceConfig {
+        proposer_policy: ProposerPolicy::Proposers,
+        ..default_governance()
+    };
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, governance)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Proposals cannot be filed under a DAO that was never created
+    let result = service_client
+        .create_proposal(dao("MadeUpDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::DaoNotFound));
+
+    let result = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::ProposerNotAllowed));
+
+    let result = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Ok(1));
+}
+
+#[tokio::test]
+async fn test_proposal_threshold() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+    remoting.system().mint_to(NEW_ADMIN, 100_000_000_000_000);
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100)), (NEW_ADMIN.into(), U256::from(10))]).await;
+    let governance = GovernanceConfig {
+        proposal_threshold: U256::from(50),
+        ..default_governance()
+    };
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, governance)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InsufficientProposerBalance { required: U256::from(50), actual: U256::from(10) }));
+
+    // Lower the threshold through governance
+    let lowered = GovernanceConfig {
+        proposal_threshold: U256::from(5),
+        ..default_governance()
+    };
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Lower threshold".into(), "Detail".into(), 10, 20, vec![ProposalAction::UpdateGovernance(lowered.clone())])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(15);
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(21);
+    service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    service_client
+        .execute_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let governance = service_client
+        .get_governance_config(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(governance, lowered);
+
+    let result = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 2".into(), "Detail 2".into(), 10, 20, vec![])
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_proposal_deposit() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    let governance = GovernanceConfig {
+        quorum: Quorum::Absolute(U256::from(1)),
+        proposal_deposit: U256::from(10),
+        ..default_governance()
+    };
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, governance)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Without an allowance the deposit cannot be locked
+    let result = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert!(result.is_err());
+
+    let mut vft_client = nexus_vft_client::NexusVft::new(remoting.clone());
+    vft_client
+        .approve(program_id, U256::from(20))
+        .send_recv(nexus_vft_id)
+        .await
+        .unwrap();
+
+    let passing_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let ignored_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 2".into(), "Detail 2".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), passing_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.deposit.unwrap().status, DepositStatus::Locked);
+
+    remoting.system().spend_blocks(15);
+    service_client
+        .vote_on_proposal(dao("TestDAO"), passing_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(21);
+    for proposal_id in [passing_id, ignored_id] {
+        service_client
+            .finalize_proposal(dao("TestDAO"), proposal_id)
+            .send_recv(program_id)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    // The proposal that missed quorum forfeits its deposit to the treasury
+    let result = service_client
+        .claim_deposit(dao("TestDAO"), ignored_id)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::DepositNotRefundable));
+
+    let treasury = service_client
+        .get_treasury(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(treasury, U256::from(10));
+
+    service_client
+        .claim_deposit(dao("TestDAO"), passing_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), passing_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.deposit.unwrap().status, DepositStatus::Claimed);
+
+    let balance = vft_client
+        .balance_of(ACTOR_ID.into())
+        .recv(nexus_vft_id)
+        .await
+        .unwrap();
+
+    assert_eq!(balance, U256::from(90));
+}
+
+#[tokio::test]
+async fn test_voting_window_limits() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
+    let governance = GovernanceConfig {
+        max_voting_delay: 10,
+        min_voting_period: 5,
+        max_voting_period: 50,
+        ..default_governance()
+    };
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, governance)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 11, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::VotingDelayTooLong));
+
+    for voting_period in [4, 51] {
+        let result = service_client
+            .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 0, voting_period, vec![])
+            .send_recv(program_id)
+            .await
+            .unwrap();
+
+        assert_eq!(result, Err(Error::InvalidVotingWindow));
+    }
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 50, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.voting_end - proposal.voting_start, 50);
+    assert_eq!(proposal.status, ProposalStatus::Pending);
+
+    // Absolute windows are held to the same limits, counted from the current block
+    let block = remoting.system().block_height();
+    let cases = [
+        (block - 1, block + 20, Error::InvalidVotingWindow),
+        (block + 5, block + 4, Error::InvalidVotingWindow),
+        (block + 100, block + 120, Error::VotingDelayTooLong),
+    ];
+    for (voting_start, voting_end, error) in cases {
+        let result = service_client
+            .create_proposal_at(dao("TestDAO"), "Proposal 2".into(), "Detail 2".into(), voting_start, voting_end, vec![])
+            .send_recv(program_id)
+            .await
+            .unwrap();
+
+        assert_eq!(result, Err(error));
+    }
+
+    let block = remoting.system().block_height();
+    let proposal_id = service_client
+        .create_proposal_at(dao("TestDAO"), "Proposal 2".into(), "Detail 2".into(), block + 5, block + 25, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.voting_start, block + 5);
+    assert_eq!(proposal.voting_end, block + 25);
+}
+
+#[tokio::test]
+async fn test_voting_closes_at_voting_end() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100)), (NEW_ADMIN.into(), U256::from(50))]).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Blocks between reading the height and a message being processed, taken from the block a member joins in
+    let block = remoting.system().block_height();
+    service_client
+        .join_dao(dao("TestDAO"))
+        .with_args(GTestArgs::new(THIRD_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let members = service_client
+        .get_members(dao("TestDAO"), None, 10)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let joined_block = members.members.iter().find(|member| member.actor == ActorId::from(THIRD_ADMIN)).unwrap().joined_block;
+    let lag = joined_block - block;
+
+    // The last block of the window still takes votes
+    remoting.system().spend_blocks(proposal.voting_end - 1 - lag - remoting.system().block_height());
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // At exactly voting_end the vote is over
+    let vote_result = service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::Against)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(vote_result, Err(Error::NotInVotingPeriod));
+
+    service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.votes_for, U256::from(100));
+    assert_eq!(proposal.votes_against, U256::zero());
+}
+
+#[tokio::test]
+async fn test_proposal_voting() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let vote_result = service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await.unwrap();;
+
+    println!("res A  = {:?}", vote_result);
+    assert!(vote_result.is_err());
+
+    remoting.system().spend_blocks(11);
+    let vote_result = service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await.unwrap();;
+
+    assert!(vote_result.is_ok());
+
+    let proposals = service_client
+        .get_proposals(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposals[0].votes_for, U256::from(100));
+
+    // A second ballot from the same account is refused
+    let vote_result = service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::Against)
+        .send_recv(program_id)
+        .await.unwrap();
+
+    assert_eq!(vote_result, Err(Error::AlreadyVoted));
+
+    let proposals = service_client
+        .get_proposals(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposals[0].votes_for, U256::from(100));
+    assert_eq!(proposals[0].votes_against, U256::zero());
+
+    let ballot = service_client
+        .get_vote(dao("TestDAO"), proposal_id, ACTOR_ID.into())
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(ballot.option, VoteOption::For);
+    assert_eq!(ballot.weight, U256::from(100));
+}
+
+#[tokio::test]
+async fn test_moved_tokens_cannot_vote_twice() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(11);
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // The tokens stay with the voter, but voting power was fixed when the proposal was created
+    let transferred = nexus_vft_client::NexusVft::new(remoting.clone())
+        .transfer(NEW_ADMIN.into(), U256::from(100))
+        .send_recv(nexus_vft_id)
+        .await
+        .unwrap();
+
+    assert!(transferred);
+
+    let vote_result = service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(vote_result, Err(Error::NoVotingPower));
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.votes_for, U256::from(100));
+
+    // A proposal created after the transfer sees the new holder
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 2".into(), "Detail 2".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(11);
+    let vote_result = service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(vote_result, Err(Error::NoVotingPower));
+
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_vote_on_overlapping_proposals() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let first_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let second_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 2".into(), "Detail 2".into(), 5, 30, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Both windows are open; the same balance counts on each proposal
+    remoting.system().spend_blocks(11);
+    service_client
+        .vote_on_proposal(dao("TestDAO"), first_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    service_client
+        .vote_on_proposal(dao("TestDAO"), second_id, VoteOption::Against)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let first = service_client
+        .get_proposal(dao("TestDAO"), first_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let second = service_client
+        .get_proposal(dao("TestDAO"), second_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(first.votes_for, U256::from(100));
+    assert_eq!(second.votes_against, U256::from(100));
+    assert_eq!(second.votes_for, U256::zero());
+
+    // Each proposal still takes a single ballot from the account
+    let vote_result = service_client
+        .vote_on_proposal(dao("TestDAO"), second_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(vote_result, Err(Error::AlreadyVoted));
+}
+
+#[tokio::test]
+async fn test_change_and_retract_vote() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(11);
+
+    // Nothing to change before a ballot is cast
+    let result = service_client
+        .change_vote(dao("TestDAO"), proposal_id, VoteOption::Against)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::NotVoted));
+
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    service_client
+        .change_vote(dao("TestDAO"), proposal_id, VoteOption::Against)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.votes_for, U256::zero());
+    assert_eq!(proposal.votes_against, U256::from(100));
+
+    service_client
+        .retract_vote(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.votes_against, U256::zero());
+
+    let ballot = service_client
+        .get_vote(dao("TestDAO"), proposal_id, ACTOR_ID.into())
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(ballot.is_none());
+}
+
+#[tokio::test]
+async fn test_proposal_finalization() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(15);
+
+    let _ = service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await.unwrap();;
+
+    // Simulate passing the voting period
+    remoting.system().spend_blocks(21);
+
+    let _ = service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    let proposals = service_client
+        .get_proposals(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(matches!(proposals[0].status, ProposalStatus::Queued { .. }));
+}
+
+#[tokio::test]
+async fn test_proposal_vetoed() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let initial_balance = vec![(ACTOR_ID.into(), U256::from(100)), (NEW_ADMIN.into(), U256::from(60))];
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), initial_balance).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(15);
+
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // 60 of 160 voting tokens is above the veto threshold
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::NoWithVeto)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(21);
+
+    service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.votes_veto, U256::from(60));
+    assert_eq!(proposal.status, ProposalStatus::Vetoed);
+}
+
+#[tokio::test]
+async fn test_proposal_quorum_not_reached() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let initial_balance = vec![(ACTOR_ID.into(), U256::from(10)), (NEW_ADMIN.into(), U256::from(90))];
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), initial_balance).await;
+
+    // Half of the total supply has to take part
+    let governance = GovernanceConfig {
+        quorum: Quorum::PercentOfSupply(50),
+        ..default_governance()
+    };
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, governance)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(15);
+
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(21);
+
+    service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::QuorumNotReached);
+}
+
+#[tokio::test]
+async fn test_proposal_execution() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // DAO tokens are off limits, the treasury can only be spent through TreasuryTransfer
+    let action = ProposalAction::SendMessage {
+        destination: nexus_vft_id,
+        payload: nexus_vft_client::nexus_vft::io::Transfer::encode_call(ACTOR_ID.into(), U256::from(1)),
+        value: 0,
+    };
+    let result = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![action])
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::ProtectedDestination));
+
+    // Ask another token program for its name once the proposal passes
+    let other_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "Other Token".to_string(), "OT".to_string(), [].to_vec()).await;
+    let action = ProposalAction::SendMessage {
+        destination: other_vft_id,
+        payload: nexus_vft_client::nexus_vft::io::Name::encode_call(),
+        value: 0,
+    };
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![action])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(15);
+
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Executing before finalization is refused
+    let result = service_client
+        .execute_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::ProposalNotQueued));
+
+    remoting.system().spend_blocks(21);
+
+    service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    service_client
+        .execute_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::Executed);
+    assert_eq!(proposal.execution_results.len(), 1);
+    assert!(matches!(proposal.execution_results[0], ActionResult::Success(_)));
+}
+
+#[tokio::test]
+async fn test_automatic_finalization() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    let governance = GovernanceConfig {
+        finalization: FinalizationMode::AutomaticWithExecution,
+        ..default_governance()
+    };
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, governance)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(proposal.deadline_scheduled);
+
+    // Only the program itself may run the deadline
+    let result = service_client
+        .process_deadline(1, proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::Unauthorized));
+
+    remoting.system().spend_blocks(15);
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Nobody calls finalize_proposal or execute_proposal
+    remoting.system().spend_blocks(21);
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::Executed);
+    assert!(!proposal.deadline_scheduled);
+}
+
+#[tokio::test]
+async fn test_cancel_proposal() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100)), (NEW_ADMIN.into(), U256::from(50))]).await;
+    let governance = GovernanceConfig {
+        proposal_deposit: U256::from(10),
+        ..default_governance()
+    };
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, governance)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let mut vft_client = nexus_vft_client::NexusVft::new(remoting.clone());
+    vft_client
+        .approve(program_id, U256::from(20))
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(nexus_vft_id)
+        .await
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // The creator can withdraw the proposal before voting starts and get the deposit back
+    service_client
+        .cancel_proposal(dao("TestDAO"), proposal_id, "Typo in the title".into())
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::Cancelled);
+    assert_eq!(proposal.deposit.unwrap().status, DepositStatus::Refundable);
+
+    service_client
+        .claim_deposit(dao("TestDAO"), proposal_id)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let balance = vft_client
+        .balance_of(NEW_ADMIN.into())
+        .recv(nexus_vft_id)
+        .await
+        .unwrap();
+
+    assert_eq!(balance, U256::from(50));
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 2".into(), "Detail 2".into(), 10, 20, vec![])
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Once voting is open only an admin can cancel
+    remoting.system().spend_blocks(15);
+
+    let result = service_client
+        .cancel_proposal(dao("TestDAO"), proposal_id, "Changed my mind".into())
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::Unauthorized));
+
+    service_client
+        .cancel_proposal(dao("TestDAO"), proposal_id, "Superseded".into())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::NotInVotingPeriod));
+}
+
+#[tokio::test]
+async fn test_proposal_drafts() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_draft(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::Draft);
+
+    // A draft takes no votes, however long it waits
+    remoting.system().spend_blocks(15);
+    let vote_result = service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(vote_result, Err(Error::NotInVotingPeriod));
+
+    // Only the creator can submit it
+    let result = service_client
+        .submit_proposal(dao("TestDAO"), proposal_id, 10, 20)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::Unauthorized));
+
+    let block = remoting.system().block_height();
+    service_client
+        .submit_proposal(dao("TestDAO"), proposal_id, 10, 20)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::Pending);
+    assert!(proposal.voting_start >= block + 10);
+    assert_eq!(proposal.voting_end, proposal.voting_start + 20);
+
+    let result = service_client
+        .submit_proposal(dao("TestDAO"), proposal_id, 10, 20)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InvalidStatusTransition));
+
+    remoting.system().spend_blocks(11);
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // A cancelled draft can no longer be submitted
+    let draft_id = service_client
+        .create_draft(dao("TestDAO"), "Proposal 2".into(), "Detail 2".into(), vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+    service_client
+        .cancel_proposal(dao("TestDAO"), draft_id, "Not needed".into())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .submit_proposal(dao("TestDAO"), draft_id, 10, 20)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InvalidStatusTransition));
+}
+
+#[tokio::test]
+async fn test_timelock_and_guardian_cancel() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    let governance = GovernanceConfig {
+        execution_delay: 50,
+        ..default_governance()
+    };
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, governance)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(15);
+
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(21);
+
+    service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Still inside the execution delay
+    let result = service_client
+        .execute_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::TimelockNotExpired));
+
+    // Only guardians may cancel
+    let result = service_client
+        .cancel_queued_proposal(dao("TestDAO"), proposal_id)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::NotGuardian));
+
+    service_client
+        .cancel_queued_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::Cancelled);
+}
+
+#[tokio::test]
+async fn test_proposal_lifecycle() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    let governance = GovernanceConfig {
+        execution_delay: 5,
+        execution_grace_period: 10,
+        ..default_governance()
+    };
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, governance)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::Pending);
+
+    remoting.system().spend_blocks(15);
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::Active);
+
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Voting is over, so the proposal no longer reads as Active even before anyone finalizes it
+    remoting.system().spend_blocks(21);
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::Succeeded);
+
+    service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::AlreadyFinalized));
+
+    // Left in the queue past the grace period, it can no longer be executed
+    remoting.system().spend_blocks(20);
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::Expired);
+
+    let result = service_client
+        .execute_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::ProposalExpired));
+}
+
+#[tokio::test]
+async fn test_treasury_deposit_and_transfer() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Let the DAO program pull tokens from the depositor
+    let mut vft_client = nexus_vft_client::NexusVft::new(remoting.clone());
+    vft_client
+        .approve(program_id, U256::from(40))
+        .send_recv(nexus_vft_id)
+        .await
+        .unwrap();
+
+    service_client
+        .deposit_to_treasury(dao("TestDAO"), U256::from(40))
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let treasury = service_client
+        .get_treasury(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(treasury, U256::from(40));
+
+    let action = ProposalAction::TreasuryTransfer {
+        to: NEW_ADMIN.into(),
+        amount: U256::from(15),
+    };
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![action])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(15);
+
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(21);
+
+    service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    service_client
+        .execute_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let treasury = service_client
+        .get_treasury(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(treasury, U256::from(25));
+
+    let recipient_balance = vft_client
+        .balance_of(NEW_ADMIN.into())
+        .recv(nexus_vft_id)
+        .await
+        .unwrap();
+
+    assert_eq!(recipient_balance, U256::from(15));
+}
+
+#[tokio::test]
+async fn test_value_treasury() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+    remoting.system().mint_to(ACTOR_ID, 100_000_000_000_000);
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .deposit_value(dao("TestDAO"))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::NoValueAttached));
+
+    // Value sent to a missing DAO or to any other call is refunded instead of being kept
+    let program_balance = remoting.system().balance_of(program_id);
+    let result = service_client
+        .deposit_value(dao("MissingDAO"))
+        .with_value(10_000_000_000_000)
+        .send_recv(program_id)
+        .await;
+
+    assert!(result.is_err());
+
+    let result = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 0".into(), "Detail 0".into(), 10, 20, vec![])
+        .with_value(10_000_000_000_000)
+        .send_recv(program_id)
+        .await;
+
+    assert!(result.is_err());
+    assert_eq!(remoting.system().balance_of(program_id), program_balance);
+
+    service_client
+        .deposit_value(dao("TestDAO"))
+        .with_value(10_000_000_000_000)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let action = ProposalAction::ValueTransfer {
+        to: NEW_ADMIN.into(),
+        amount: 4_000_000_000_000,
+        gas_limit: 0,
+    };
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![action])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(15);
+
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(21);
+
+    service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    service_client
+        .execute_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let value_treasury = service_client
+        .get_value_treasury(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(value_treasury, 6_000_000_000_000);
+
+    // Value attached to a message is paid from the same ledger and cannot exceed it
+    let other_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "Other Token".to_string(), "OT".to_string(), [].to_vec()).await;
+    let action = ProposalAction::SendMessage {
+        destination: other_vft_id,
+        payload: nexus_vft_client::nexus_vft::io::Name::encode_call(),
+        value: 7_000_000_000_000,
+    };
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 2".into(), "Detail 2".into(), 10, 20, vec![action])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(15);
+
+    service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(21);
+
+    service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    service_client
+        .execute_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal = service_client
+        .get_proposal(dao("TestDAO"), proposal_id)
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::ExecutionFailed);
+
+    let value_treasury = service_client
+        .get_value_treasury(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(value_treasury, 6_000_000_000_000);
+}
+
+#[tokio::test]
+async fn test_vote_on_nonexistent_proposal() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .vote_on_proposal(dao("TestDAO"), 999, VoteOption::For)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::ProposalNotFound));
+}
+
+#[tokio::test]
+async fn test_typed_errors() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let result = service_client
+        .get_proposals(dao("MissingDAO"))
+        .recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::DaoNotFound));
+
+    let result = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 0, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::InvalidVotingWindow));
+
+    // Proposals cannot message the program itself
+    let action = ProposalAction::SendMessage {
+        destination: program_id,
+        payload: vec![],
+        value: 0,
+    };
+    let result = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![action])
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::ProtectedDestination));
+
+    let result = service_client
+        .add_admin(dao("TestDAO"), NEW_ADMIN.into())
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::Unauthorized));
+
+    let result = service_client
+        .add_admin(dao("TestDAO"), ACTOR_ID.into())
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(result, Err(Error::AlreadyAdmin));
+}
+
+#[tokio::test]
+async fn test_vote_without_tokens_rejected() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), vec![(ACTOR_ID.into(), U256::from(100))]).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(11);
+
+    // NEW_ADMIN holds no governance tokens
+    let vote_result = service_client
+        .vote_on_proposal(dao("TestDAO"), proposal_id, VoteOption::For)
+        .with_args(GTestArgs::new(NEW_ADMIN.into()))
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert_eq!(vote_result, Err(Error::NoVotingPower));
+
+    let proposals = service_client
+        .get_proposals(dao("TestDAO"))
+        .recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(proposals[0].votes_for, U256::zero());
+}
+
+#[tokio::test]
+async fn test_finalize_proposal_before_voting_end() {
+    let remoting = GTestRemoting::new(ACTOR_ID.into());
+    remoting.system().init_logger();
+
+    let program_code_id = remoting.system().submit_code(nexus_dao::WASM_BINARY);
+
+    let program_factory = nexus_dao_client::NexusDaoFactory::new(remoting.clone());
+    let program_id = program_factory
+        .new()
+        .send_recv(program_code_id, b"salt")
+        .await
+        .unwrap();
+
+    let mut service_client = nexus_dao_client::NexusDao::new(remoting.clone());
+
+    let nexus_vft_id = get_vft_id(remoting.clone(), ACTOR_ID.into(), "DAO Token".to_string(), "DT".to_string(), [].to_vec()).await;
+    service_client
+        .create_dao("TestDAO".into(), "A test DAO".into(), nexus_vft_id, default_governance())
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposal_id = service_client
+        .create_proposal(dao("TestDAO"), "Proposal 1".into(), "Detail 1".into(), 10, 20, vec![])
+        .send_recv(program_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    remoting.system().spend_blocks(5);
+
+    let result = service_client
+        .finalize_proposal(dao("TestDAO"), proposal_id)
+        .send_recv(program_id)
+        .await
+        .unwrap();
+
+    assert!(result.is_err());
+}
+
+use nexus_vft_client::traits::*;
+
+fn dao(name: &str) -> DaoRef {
+    DaoRef::Name(name.to_string())
+}
+
+fn default_governance() -> GovernanceConfig {
+    GovernanceConfig {
+        quorum: Quorum::Absolute(U256::zero()),
+        approval_threshold: ApprovalThreshold::SimpleMajority,
+        tie_break: TieBreak::Reject,
+        veto_threshold_percent: 33,
+        max_voting_delay: 1000,
+        min_voting_period: 1,
+        max_voting_period: 1000,
+        execution_delay: 0,
+        execution_grace_period: 0,
+        proposer_policy: ProposerPolicy::Anyone,
+        membership_policy: MembershipPolicy::Open,
+        proposal_threshold: U256::zero(),
+        proposal_deposit: U256::zero(),
+        finalization: FinalizationMode::Manual,
+    }
+}
 
 async fn get_vft_id(remoting: GTestRemoting, actor_id: ActorId, name: String, symbol: String, initial_balance: Vec<(ActorId, U256)>) -> ActorId {
 